@@ -0,0 +1,1014 @@
+//! the viewport camera. holding the right mouse button flies it around with WASD/QE in every
+//! navigation scheme (scrolling meanwhile changes how fast, see [`FlyCamera`]), and depending on
+//! the [`NavigationScheme`] dragging with the left or middle button orbits it around its
+//! [`CameraPivot`] or pans it. the scroll wheel dollies toward the pivot. the pivot moves to the
+//! selected entity whenever an orbit starts.
+//! View > Frame Selected (F, or double-clicking a row in the hierarchy) glides the camera over to
+//! where the selected entity and its children fill the viewport.
+//! the view cube in the corner of the viewport (or the numpad, like in Blender) turns the camera
+//! to look along an axis, and switches between perspective and orthographic. going orthographic
+//! narrows the field of view while backing off so what's at the pivot stays the same size, then
+//! swaps the projection once there's barely any perspective left, and going back does the
+//! opposite.
+//! there can be several viewports, each with its own camera (Window > Workspaces > Quad View
+//! shows four). the mouse only moves the camera of the viewport it's over, and the menu, the
+//! keyboard shortcuts and the status bar go to the [`ActiveViewport`], the one it was last over.
+use bevy::core_pipeline::clear_color::ClearColorConfig;
+use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use bevy::render::camera::{self, RenderTarget, ScalingMode};
+use bevy::render::primitives::Aabb;
+use bevy::window::WindowRef;
+use bevy_mod_picking::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::floating::NodeWindows;
+use crate::menu::MenuAction;
+use crate::scene::EditorOnly;
+use crate::selection::Selection;
+use crate::theme::EditorTheme;
+
+/// How long framing the selection, turning to an axis or switching projections takes, in seconds.
+const TRANSITION_DURATION: f32 = 0.3;
+/// The field of view perspective gets narrowed to before switching to orthographic.
+const ORTHOGRAPHIC_FOV: f32 = 5.0 * std::f32::consts::PI / 180.0;
+
+/// How quickly the fly cam gets up to speed and slows back down, higher is snappier.
+const FLY_ACCELERATION: f32 = 10.0;
+/// Below this many units per second a fly cam that's been let go of stops.
+const FLY_STOP_SPEED: f32 = 0.01;
+/// Holding Shift multiplies the fly speed by this.
+const FLY_FAST: f32 = 5.0;
+/// Holding Alt multiplies the fly speed by this.
+const FLY_SLOW: f32 = 0.2;
+
+/// How many viewport panels there can be.
+pub const VIEWPORT_COUNT: usize = 4;
+
+/// The UI node a viewport camera renders into.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Viewport {
+    pub index: usize,
+}
+
+/// The camera that renders the scene into the [`Viewport`] node with the same index.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ViewportCamera {
+    pub index: usize,
+}
+
+/// The index of the viewport the mouse was last over.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ActiveViewport(pub usize);
+
+/// The point a [`ViewportCamera`] orbits around and dollies toward.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+pub struct CameraPivot(pub Vec3);
+
+/// How a [`ViewportCamera`] flies. The speed is changed by scrolling while flying, and shown in
+/// the status bar. It eases up to speed, and coasts to a stop once the right mouse button is let
+/// go.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct FlyCamera {
+    /// units per second, before Shift or Alt
+    pub speed: f32,
+    pub velocity: Vec3,
+}
+
+impl Default for FlyCamera {
+    fn default() -> Self {
+        Self {
+            speed: 5.0,
+            velocity: Vec3::ZERO,
+        }
+    }
+}
+
+impl FlyCamera {
+    pub const MIN_SPEED: f32 = 0.05;
+    pub const MAX_SPEED: f32 = 500.0;
+}
+
+/// The status bar text showing the [`FlyCamera`]'s speed, of the [`ActiveViewport`]'s camera.
+#[derive(Component, Debug)]
+pub struct CameraSpeedText;
+
+/// Where a [`ViewportCamera`] is looking from, described so it can be eased between two of them
+/// and between perspective and orthographic.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CameraView {
+    pub pivot: Vec3,
+    pub rotation: Quat,
+    /// how much can be seen at the pivot, top to bottom
+    pub height: f32,
+    /// ignored while orthographic
+    pub fov: f32,
+}
+
+impl CameraView {
+    pub fn new(transform: &Transform, pivot: &CameraPivot, projection: &Projection) -> Self {
+        let distance = transform.translation.distance(pivot.0);
+        let (height, fov) = match projection {
+            Projection::Perspective(perspective) => (
+                2.0 * distance * (perspective.fov / 2.0).tan(),
+                perspective.fov,
+            ),
+            Projection::Orthographic(orthographic) => {
+                (orthographic_height(orthographic), ORTHOGRAPHIC_FOV)
+            }
+        };
+        Self {
+            pivot: pivot.0,
+            rotation: transform.rotation,
+            height,
+            fov,
+        }
+    }
+
+    fn lerp(&self, to: &Self, t: f32) -> Self {
+        Self {
+            pivot: self.pivot.lerp(to.pivot, t),
+            rotation: self.rotation.slerp(to.rotation, t),
+            height: self.height + (to.height - self.height) * t,
+            fov: self.fov + (to.fov - self.fov) * t,
+        }
+    }
+
+    /// Puts the camera here. Orthographic cameras stay `height` away from the pivot, which doesn't
+    /// change what they show but keeps orbiting and dollying working the same.
+    pub fn apply(&self, transform: &mut Transform, projection: &mut Projection) {
+        let distance = match projection {
+            Projection::Perspective(perspective) => {
+                perspective.fov = self.fov;
+                self.height / 2.0 / (self.fov / 2.0).tan()
+            }
+            Projection::Orthographic(orthographic) => {
+                orthographic.scaling_mode = ScalingMode::FixedVertical(self.height);
+                orthographic.scale = 1.0;
+                self.height
+            }
+        };
+        transform.rotation = self.rotation;
+        transform.translation = self.pivot + self.rotation * Vec3::Z * distance;
+    }
+}
+
+fn orthographic_height(orthographic: &OrthographicProjection) -> f32 {
+    match orthographic.scaling_mode {
+        ScalingMode::FixedVertical(height) => height * orthographic.scale,
+        _ => orthographic.area.height(),
+    }
+}
+
+/// An orthographic projection that shows `height` at the pivot. It sees behind the camera too, so
+/// nothing between it and the pivot gets clipped.
+fn orthographic(height: f32) -> Projection {
+    Projection::Orthographic(OrthographicProjection {
+        scaling_mode: ScalingMode::FixedVertical(height),
+        near: -1000.0,
+        far: 1000.0,
+        ..default()
+    })
+}
+
+/// A [`ViewportCamera`] easing from one view to another, see [`animate_camera_transitions`].
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct CameraTransition {
+    pub from: CameraView,
+    pub to: CameraView,
+    /// switches to an orthographic projection once it gets to `to`
+    pub orthographic: bool,
+    /// seconds since it started
+    pub elapsed: f32,
+}
+
+impl CameraTransition {
+    pub fn new(from: CameraView, to: CameraView) -> Self {
+        Self {
+            from,
+            to,
+            orthographic: false,
+            elapsed: 0.0,
+        }
+    }
+}
+
+/// Sent when a drag starts moving the camera of the viewport `index`.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CameraGrabbed {
+    pub index: usize,
+}
+
+/// An axis the camera can look along, from the view cube or the numpad.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AxisView {
+    Top,
+    Bottom,
+    Front,
+    Back,
+    Right,
+    Left,
+}
+
+impl AxisView {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Top => "Top",
+            Self::Bottom => "Bottom",
+            Self::Front => "Front",
+            Self::Back => "Back",
+            Self::Right => "Right",
+            Self::Left => "Left",
+        }
+    }
+
+    /// The camera's rotation looking along the axis, toward -Z for the front.
+    pub fn rotation(self) -> Quat {
+        use std::f32::consts::{FRAC_PI_2, PI};
+        match self {
+            Self::Top => Quat::from_rotation_x(-FRAC_PI_2),
+            Self::Bottom => Quat::from_rotation_x(FRAC_PI_2),
+            Self::Front => Quat::IDENTITY,
+            Self::Back => Quat::from_rotation_y(PI),
+            Self::Right => Quat::from_rotation_y(FRAC_PI_2),
+            Self::Left => Quat::from_rotation_y(-FRAC_PI_2),
+        }
+    }
+}
+
+/// The buttons in the corner of the viewport that snap the camera to an axis, with `None` for the
+/// one switching between perspective and orthographic.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ViewCubeButton(pub Option<AxisView>);
+
+/// The text of the perspective/orthographic button of the view cube in the viewport with this
+/// index.
+#[derive(Component, Debug)]
+pub struct ViewCubeProjectionText(pub usize);
+
+/// Which mouse buttons orbit and pan the viewport camera. Right-dragging flies it in all of them.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NavigationScheme {
+    /// Alt+left drag orbits, middle drag pans
+    #[default]
+    Unity,
+    /// middle drag orbits, Shift+middle drag pans
+    Blender,
+    /// only the right mouse fly cam, and the scroll wheel
+    Fly,
+}
+
+impl NavigationScheme {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Unity => "Unity",
+            Self::Blender => "Blender",
+            Self::Fly => "Fly Only",
+        }
+    }
+
+    /// What pressing `button` starts, given the modifiers held.
+    fn drag(self, button: MouseButton, alt: bool, shift: bool) -> Option<CameraDrag> {
+        match (self, button) {
+            (_, MouseButton::Right) => Some(CameraDrag::Fly),
+            (Self::Unity, MouseButton::Left) if alt => Some(CameraDrag::Orbit),
+            (Self::Unity, MouseButton::Middle) => Some(CameraDrag::Pan),
+            (Self::Blender, MouseButton::Middle) if shift => Some(CameraDrag::Pan),
+            (Self::Blender, MouseButton::Middle) => Some(CameraDrag::Orbit),
+            _ => None,
+        }
+    }
+}
+
+/// What the mouse button held since it was pressed over the viewport is doing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CameraDrag {
+    Fly,
+    Orbit,
+    Pan,
+}
+
+/// Where an entity is, the center of its bounds if it has any.
+pub fn entity_center(transform: &GlobalTransform, aabb: Option<&Aabb>) -> Vec3 {
+    aabb.map_or(transform.translation(), |aabb| {
+        transform.transform_point(aabb.center.into())
+    })
+}
+
+/// Turns `transform` around `pivot` by mouse movement, keeping it upright.
+fn orbit(transform: &mut Transform, pivot: Vec3, delta: Vec2) {
+    let sensitivity = 0.005;
+    let (yaw, pitch, _) = transform.rotation.to_euler(EulerRot::YXZ);
+    let yaw = yaw - delta.x * sensitivity;
+    let pitch = (pitch - delta.y * sensitivity).clamp(-1.54, 1.54);
+    let rotation = Quat::from_axis_angle(Vec3::Y, yaw) * Quat::from_axis_angle(Vec3::X, pitch);
+    let turn = rotation * transform.rotation.inverse();
+    transform.translation = pivot + turn * (transform.translation - pivot);
+    transform.rotation = rotation;
+}
+
+/// Spawns the camera for the viewport `index`. The main one starts looking at the origin at an
+/// angle, and the others look down the top, front and right like Blender's quad view.
+pub fn spawn_viewport_camera(commands: &mut Commands, index: usize) -> Entity {
+    let (transform, projection) = match index {
+        0 => (
+            Transform::from_xyz(-2.5, 4.5, 9.0).looking_at(Vec3::ZERO, Vec3::Y),
+            Projection::default(),
+        ),
+        _ => {
+            let axis = [AxisView::Top, AxisView::Front, AxisView::Right][(index - 1) % 3];
+            let mut transform = Transform::default();
+            let mut projection = orthographic(10.0);
+            let view = CameraView {
+                pivot: Vec3::ZERO,
+                rotation: axis.rotation(),
+                height: 10.0,
+                fov: ORTHOGRAPHIC_FOV,
+            };
+            view.apply(&mut transform, &mut projection);
+            (transform, projection)
+        }
+    };
+    let name = match index {
+        0 => "Viewport Camera".to_string(),
+        _ => format!("Viewport Camera {}", index + 1),
+    };
+    commands
+        .spawn((
+            Camera3dBundle {
+                transform,
+                projection,
+                camera: Camera {
+                    // after the UI camera, which has the default order of 0, and each other
+                    order: 1 + index as isize,
+                    // turned on by update_viewport_cameras once its viewport is laid out
+                    is_active: false,
+                    ..default()
+                },
+                camera_3d: Camera3d {
+                    // don't clear, the UI camera already cleared the window
+                    clear_color: ClearColorConfig::None,
+                    ..default()
+                },
+                ..default()
+            },
+            UiCameraConfig { show_ui: false },
+            ViewportCamera { index },
+            CameraPivot(Vec3::ZERO),
+            FlyCamera::default(),
+            Name::new(name),
+            EditorOnly,
+        ))
+        .id()
+}
+
+/// Spawns cameras for viewports that don't have one yet, like when a second viewport panel gets
+/// opened for the first time.
+pub fn spawn_viewport_cameras(
+    mut commands: Commands,
+    viewports: Query<&Viewport, Added<Viewport>>,
+    cameras: Query<&ViewportCamera>,
+) {
+    let mut indices: Vec<_> = cameras.iter().map(|camera| camera.index).collect();
+    for viewport in &viewports {
+        if !indices.contains(&viewport.index) {
+            spawn_viewport_camera(&mut commands, viewport.index);
+            indices.push(viewport.index);
+        }
+    }
+}
+
+/// Where a viewport node is in the window it shows up in, in that window's physical pixels.
+fn viewport_rect(
+    node: &Node,
+    transform: &GlobalTransform,
+    offset: Vec2,
+    scale_factor: f64,
+    ui_scale: f64,
+) -> Rect {
+    let rect = node.logical_rect(transform);
+    let scale = (scale_factor * ui_scale) as f32;
+    Rect::from_corners((rect.min - offset) * scale, (rect.max - offset) * scale)
+}
+
+/// Fits each camera to its viewport node, and turns off the ones whose viewport isn't showing,
+/// like while its tab isn't the active one. Viewports in floating panels get drawn into the
+/// panel's window.
+pub fn update_viewport_cameras(
+    viewports: Query<(Entity, &Viewport, &Node, &GlobalTransform)>,
+    ui_scale: Res<UiScale>,
+    windows: Query<&Window>,
+    node_windows: NodeWindows,
+    mut cameras: Query<(&ViewportCamera, &mut Camera)>,
+) {
+    for (viewport_camera, mut camera) in &mut cameras {
+        let shown = viewports
+            .iter()
+            .find(|(_, viewport, ..)| viewport.index == viewport_camera.index)
+            .and_then(|(entity, _, node, transform)| {
+                let (window, offset) = node_windows.get(entity)?;
+                let scale_factor = windows.get(window).map_or(1.0, Window::scale_factor);
+                let rect = viewport_rect(node, transform, offset, scale_factor, ui_scale.0);
+                Some((window, rect))
+            })
+            .filter(|(_, rect)| rect.width() > 0.0 && rect.height() > 0.0);
+        let Some((window, physical_rect)) = shown else {
+            if camera.is_active {
+                camera.is_active = false;
+            }
+            continue;
+        };
+        camera.target = RenderTarget::Window(if node_windows.is_primary(window) {
+            WindowRef::Primary
+        } else {
+            WindowRef::Entity(window)
+        });
+        camera.is_active = true;
+        camera.viewport = Some(camera::Viewport {
+            physical_position: UVec2::new(physical_rect.min.x as u32, physical_rect.min.y as u32),
+            physical_size: UVec2::new(physical_rect.width() as u32, physical_rect.height() as u32),
+            ..default()
+        });
+    }
+}
+
+/// Grabs the cursor in `window` if `grab`, and lets go of it in every other window.
+fn grab_cursor(windows: &mut Query<(Entity, &mut Window)>, window: Option<Entity>, grab: bool) {
+    for (entity, mut other) in windows {
+        let grab_mode = if grab && Some(entity) == window {
+            bevy::window::CursorGrabMode::Locked
+        } else {
+            bevy::window::CursorGrabMode::None
+        };
+        if other.cursor.grab_mode != grab_mode {
+            other.cursor.grab_mode = grab_mode;
+            other.cursor.visible = grab_mode == bevy::window::CursorGrabMode::None;
+        }
+    }
+}
+
+/// Moves the camera of the viewport under the mouse, or of the one a drag started in.
+pub fn update_camera(
+    viewports: Query<(Entity, &Viewport, &Node, &GlobalTransform)>,
+    ui_scale: Res<UiScale>,
+    mut cameras: Query<(
+        &ViewportCamera,
+        &mut Transform,
+        &mut Projection,
+        &mut CameraPivot,
+        &mut FlyCamera,
+    )>,
+    keyboard_input: Res<Input<KeyCode>>,
+    buttons: Res<Input<MouseButton>>,
+    mut windows: Query<(Entity, &mut Window)>,
+    node_windows: NodeWindows,
+    mut motion_evr: EventReader<MouseMotion>,
+    mut wheel: EventReader<MouseWheel>,
+    scheme: Res<NavigationScheme>,
+    selection: Res<Selection>,
+    selected: Query<(&GlobalTransform, Option<&Aabb>)>,
+    mut active: ResMut<ActiveViewport>,
+    // the viewport it started in, and with which button
+    mut drag: Local<Option<(usize, MouseButton, CameraDrag)>>,
+    mut grabbed: EventWriter<CameraGrabbed>,
+    time: Res<Time>,
+) {
+    // there's no viewport while its tab isn't the active one, and floating panels show theirs in
+    // their own window
+    let mut viewport_windows = Vec::new();
+    let mut hovered = None;
+    for (entity, viewport, node, transform) in &viewports {
+        let Some((window, offset)) = node_windows.get(entity) else {
+            continue;
+        };
+        let Ok((_, window_ref)) = windows.get(window) else {
+            continue;
+        };
+        viewport_windows.push((viewport.index, window));
+        let rect = viewport_rect(
+            node,
+            transform,
+            offset,
+            window_ref.scale_factor(),
+            ui_scale.0,
+        );
+        if window_ref
+            .physical_cursor_position()
+            .is_some_and(|cursor| rect.contains(cursor))
+        {
+            hovered = hovered.or(Some(viewport.index));
+        }
+    }
+
+    // drags only start over a viewport, and keep going wherever the mouse goes until released
+    if drag.is_some_and(|(_, button, _)| !buttons.pressed(button)) {
+        *drag = None;
+    }
+    let mut orbit_started = false;
+    if let (None, Some(index)) = (*drag, hovered) {
+        if active.0 != index {
+            active.0 = index;
+        }
+        let alt = keyboard_input.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]);
+        let shift = keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        *drag = buttons
+            .get_just_pressed()
+            .find_map(|button| Some((index, *button, scheme.drag(*button, alt, shift)?)));
+        orbit_started = drag.is_some_and(|(_, _, drag)| drag == CameraDrag::Orbit);
+        if drag.is_some() {
+            grabbed.send(CameraGrabbed { index });
+        }
+    }
+    let flying = drag.and_then(|(index, _, drag)| (drag == CameraDrag::Fly).then_some(index));
+    // cameras that were let go of coast to a stop, unless another kind of drag takes them over
+    let dt = time.delta_seconds();
+    for (camera, mut transform, _, mut pivot, mut fly) in &mut cameras {
+        if flying == Some(camera.index) || fly.velocity == Vec3::ZERO {
+            continue;
+        }
+        fly.velocity *= (-FLY_ACCELERATION * dt).exp();
+        if drag.is_some_and(|(index, ..)| index == camera.index)
+            || fly.velocity.length() < FLY_STOP_SPEED
+        {
+            fly.velocity = Vec3::ZERO;
+            continue;
+        }
+        transform.translation += fly.velocity * dt;
+        pivot.0 += fly.velocity * dt;
+    }
+
+    let scrolled: f32 = wheel
+        .read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / 100.0,
+        })
+        .sum();
+    let camera = drag.map(|(index, ..)| index).or(hovered).and_then(|index| {
+        cameras
+            .iter_mut()
+            .find(|(camera, ..)| camera.index == index)
+    });
+    let Some((camera, mut camera_transform, mut projection, mut pivot, mut fly)) = camera else {
+        grab_cursor(&mut windows, None, false);
+        // so the next drag doesn't start with a jump
+        motion_evr.clear();
+        return;
+    };
+    let window = viewport_windows
+        .iter()
+        .find(|(index, _)| *index == camera.index)
+        .map(|(_, window)| *window);
+    if orbit_started {
+        if let Some((transform, aabb)) = selection.entity.and_then(|e| selected.get(e).ok()) {
+            pivot.0 = entity_center(transform, aabb);
+        }
+    }
+
+    let distance = camera_transform.translation.distance(pivot.0).max(0.05);
+    let flying = flying.is_some();
+    if scrolled != 0.0 && flying {
+        fly.speed =
+            (fly.speed * 1.2f32.powf(scrolled)).clamp(FlyCamera::MIN_SPEED, FlyCamera::MAX_SPEED);
+    } else if scrolled != 0.0 && hovered == Some(camera.index) {
+        let closer = (distance * 0.85f32.powf(scrolled)).max(0.05);
+        let forward = camera_transform.forward();
+        camera_transform.translation += forward * (distance - closer);
+        // moving doesn't make things look any bigger without perspective
+        if let Projection::Orthographic(orthographic) = &mut *projection {
+            let height = orthographic_height(orthographic) * closer / distance;
+            orthographic.scaling_mode = ScalingMode::FixedVertical(height);
+            orthographic.scale = 1.0;
+        }
+    }
+
+    match drag.map(|(_, _, drag)| drag) {
+        Some(CameraDrag::Orbit) => {
+            let delta: Vec2 = motion_evr.read().map(|event| event.delta).sum();
+            orbit(&mut camera_transform, pivot.0, delta);
+        }
+        Some(CameraDrag::Pan) => {
+            let delta: Vec2 = motion_evr.read().map(|event| event.delta).sum();
+            // further away things move slower on screen, so pan faster to keep up with the mouse
+            let pan = (camera_transform.up() * delta.y - camera_transform.right() * delta.x)
+                * distance
+                * 0.0015;
+            camera_transform.translation += pan;
+            pivot.0 += pan;
+        }
+        _ => {}
+    }
+    if drag.is_some_and(|(_, _, drag)| drag != CameraDrag::Fly) {
+        grab_cursor(&mut windows, window, true);
+        return;
+    }
+
+    // standard fly cam: WASD and QE move it and the mouse turns it while the right button is held,
+    // with the cursor locked to the viewport's window
+    if flying {
+        grab_cursor(&mut windows, window, true);
+
+        let mut delta = Vec3::ZERO;
+        let forward = -camera_transform.local_z();
+        let right = camera_transform.local_x();
+        let up = camera_transform.local_y();
+
+        let sensitivity = 0.00012;
+
+        if keyboard_input.pressed(KeyCode::W) {
+            delta += forward;
+        }
+        if keyboard_input.pressed(KeyCode::S) {
+            delta -= forward;
+        }
+        if keyboard_input.pressed(KeyCode::A) {
+            delta -= right;
+        }
+        if keyboard_input.pressed(KeyCode::D) {
+            delta += right;
+        }
+        if keyboard_input.pressed(KeyCode::Q) {
+            delta -= up;
+        }
+        if keyboard_input.pressed(KeyCode::E) {
+            delta += up;
+        }
+
+        // either Shift multiplies the speed by FLY_FAST and either Alt by FLY_SLOW. holding both
+        // applies both
+        let speed = fly.speed
+            * if keyboard_input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+                FLY_FAST
+            } else {
+                1.0
+            }
+            * if keyboard_input.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]) {
+                FLY_SLOW
+            } else {
+                1.0
+            };
+
+        // ease toward the speed the keys ask for, the same on any frame rate
+        let target = delta.normalize_or_zero() * speed;
+        fly.velocity = fly
+            .velocity
+            .lerp(target, 1.0 - (-FLY_ACCELERATION * dt).exp());
+
+        let mut transform = *camera_transform;
+        transform.translation += fly.velocity * dt;
+
+        for ev in motion_evr.read() {
+            let (mut yaw, mut pitch, _) = transform.rotation.to_euler(EulerRot::YXZ);
+
+            // Using smallest of height or width ensures equal vertical and horizontal sensitivity
+            let window_scale = window
+                .and_then(|window| windows.get(window).ok())
+                .map_or(0.0, |(_, window)| window.height().min(window.width()));
+            pitch -= (sensitivity * ev.delta.y * window_scale).to_radians();
+            yaw -= (sensitivity * ev.delta.x * window_scale).to_radians();
+
+            pitch = pitch.clamp(-1.54, 1.54);
+
+            // Order is important to prevent unintended roll
+            transform.rotation =
+                Quat::from_axis_angle(Vec3::Y, yaw) * Quat::from_axis_angle(Vec3::X, pitch);
+        }
+
+        *camera_transform = transform;
+        // the pivot stays in front of the camera as it flies
+        pivot.0 = transform.translation + transform.forward() * distance;
+    } else {
+        grab_cursor(&mut windows, window, false);
+        // so the next drag doesn't start with a jump
+        motion_evr.clear();
+    }
+}
+
+pub fn update_camera_speed_text(
+    cameras: Query<(&ViewportCamera, &FlyCamera)>,
+    mut texts: Query<&mut Text, With<CameraSpeedText>>,
+    active: Res<ActiveViewport>,
+) {
+    let Some((_, fly)) = cameras.iter().find(|(camera, _)| camera.index == active.0) else {
+        return;
+    };
+    let value = format!("Fly speed {:.2}", fly.speed);
+    for mut text in &mut texts {
+        if text.sections[0].value != value {
+            text.sections[0].value = value.clone();
+        }
+    }
+}
+
+/// The world space bounds of `entity` and its descendants, from their `Aabb`s. `None` if none of
+/// them have one.
+fn hierarchy_bounds(
+    entity: Entity,
+    children: &Query<&Children>,
+    bounds: &Query<(&GlobalTransform, Option<&Aabb>)>,
+) -> Option<(Vec3, Vec3)> {
+    std::iter::once(entity)
+        .chain(children.iter_descendants(entity))
+        .filter_map(|entity| match bounds.get(entity) {
+            Ok((transform, Some(aabb))) => Some((transform, aabb)),
+            _ => None,
+        })
+        .flat_map(|(transform, aabb)| {
+            let (center, half) = (Vec3::from(aabb.center), Vec3::from(aabb.half_extents));
+            [-1.0, 1.0].into_iter().flat_map(move |x| {
+                [-1.0, 1.0].into_iter().flat_map(move |y| {
+                    [-1.0, 1.0]
+                        .into_iter()
+                        .map(move |z| transform.transform_point(center + half * Vec3::new(x, y, z)))
+                })
+            })
+        })
+        .fold(None, |bounds, corner| {
+            let (min, max) = bounds.unwrap_or((corner, corner));
+            Some((min.min(corner), max.max(corner)))
+        })
+}
+
+/// Starts moving the active viewport's camera so the selection fills it when Frame Selected is
+/// picked. It keeps looking the same way, and backs off far enough for a sphere around the
+/// selection's bounds to fit in both the vertical and horizontal field of view.
+pub fn frame_selected(
+    mut commands: Commands,
+    mut actions: EventReader<MenuAction>,
+    selection: Res<Selection>,
+    children: Query<&Children>,
+    bounds: Query<(&GlobalTransform, Option<&Aabb>)>,
+    mut cameras: Query<(
+        Entity,
+        &ViewportCamera,
+        &Camera,
+        &Projection,
+        &Transform,
+        &mut CameraPivot,
+    )>,
+    active: Res<ActiveViewport>,
+) {
+    if !actions
+        .read()
+        .any(|action| *action == MenuAction::FrameSelected)
+    {
+        return;
+    }
+    let Some(entity) = selection.entity else {
+        return;
+    };
+    // things without bounds, like lights, get framed as if they were a unit cube
+    let (center, radius) = match hierarchy_bounds(entity, &children, &bounds) {
+        Some((min, max)) => ((min + max) / 2.0, (max - min).length() / 2.0),
+        None => match bounds.get(entity) {
+            Ok((transform, _)) => (transform.translation(), 0.87),
+            Err(_) => return,
+        },
+    };
+    for (camera_entity, _, camera, projection, transform, mut pivot) in cameras
+        .iter_mut()
+        .filter(|(_, camera, ..)| camera.index == active.0)
+    {
+        let aspect = camera
+            .logical_viewport_size()
+            .filter(|size| size.y > 0.0)
+            .map_or(1.0, |size| size.x / size.y);
+        let from = CameraView::new(transform, &pivot, projection);
+        let radius = radius.max(0.01);
+        let height = match projection {
+            Projection::Perspective(perspective) => {
+                let vertical = perspective.fov / 2.0;
+                let horizontal = ((perspective.fov / 2.0).tan() * aspect).atan();
+                let distance = radius / vertical.min(horizontal).sin();
+                2.0 * distance * vertical.tan()
+            }
+            // the sphere's diameter has to fit across the narrower side
+            Projection::Orthographic(_) => 2.0 * radius * (1.0 / aspect).max(1.0),
+        };
+        let to = CameraView {
+            pivot: center,
+            height,
+            ..from
+        };
+        pivot.0 = center;
+        commands
+            .entity(camera_entity)
+            .insert(CameraTransition::new(from, to));
+    }
+}
+
+/// Turns the active viewport's camera to look along an axis, or switches it between perspective
+/// and orthographic, when that's picked from the View menu or the view cube.
+pub fn snap_camera_views(
+    mut commands: Commands,
+    mut actions: EventReader<MenuAction>,
+    mut cameras: Query<(
+        Entity,
+        &ViewportCamera,
+        &mut Transform,
+        &mut Projection,
+        &CameraPivot,
+    )>,
+    active: Res<ActiveViewport>,
+) {
+    for action in actions.read() {
+        for (entity, _, mut transform, mut projection, pivot) in cameras
+            .iter_mut()
+            .filter(|(_, camera, ..)| camera.index == active.0)
+        {
+            let from = CameraView::new(&transform, pivot, &projection);
+            let transition = match action {
+                MenuAction::SetView(axis) => CameraTransition::new(
+                    from,
+                    CameraView {
+                        rotation: axis.rotation(),
+                        ..from
+                    },
+                ),
+                MenuAction::ToggleOrthographic => match *projection {
+                    Projection::Perspective(_) => CameraTransition {
+                        orthographic: true,
+                        ..CameraTransition::new(
+                            from,
+                            CameraView {
+                                fov: ORTHOGRAPHIC_FOV,
+                                ..from
+                            },
+                        )
+                    },
+                    // back to a barely perspective one right away, then widen it
+                    Projection::Orthographic(_) => {
+                        *projection = Projection::Perspective(PerspectiveProjection {
+                            fov: ORTHOGRAPHIC_FOV,
+                            ..default()
+                        });
+                        from.apply(&mut transform, &mut projection);
+                        CameraTransition::new(
+                            from,
+                            CameraView {
+                                fov: PerspectiveProjection::default().fov,
+                                ..from
+                            },
+                        )
+                    }
+                },
+                _ => continue,
+            };
+            commands.entity(entity).insert(transition);
+        }
+    }
+}
+
+/// Moves cameras along their [`CameraTransition`], easing in and out. Starting to drag the camera
+/// stops it where it is.
+pub fn animate_camera_transitions(
+    mut commands: Commands,
+    mut cameras: Query<(
+        Entity,
+        &ViewportCamera,
+        &mut Transform,
+        &mut Projection,
+        &mut CameraPivot,
+        &mut CameraTransition,
+    )>,
+    mut grabs: EventReader<CameraGrabbed>,
+    time: Res<Time>,
+) {
+    let grabbed: Vec<usize> = grabs.read().map(|grab| grab.index).collect();
+    for (entity, camera, mut transform, mut projection, mut pivot, mut transition) in &mut cameras {
+        transition.elapsed += time.delta_seconds();
+        let t = (transition.elapsed / TRANSITION_DURATION).min(1.0);
+        let eased = t * t * (3.0 - 2.0 * t);
+        let view = transition.from.lerp(&transition.to, eased);
+        pivot.0 = view.pivot;
+        view.apply(&mut transform, &mut projection);
+        if t >= 1.0 || grabbed.contains(&camera.index) {
+            if transition.orthographic {
+                *projection = orthographic(view.height);
+                view.apply(&mut transform, &mut projection);
+            }
+            commands.entity(entity).remove::<CameraTransition>();
+        }
+    }
+}
+
+pub fn spawn_view_cube(builder: &mut ChildBuilder, theme: &EditorTheme, index: usize) {
+    let button = |builder: &mut ChildBuilder, label: &str, view: Option<AxisView>| {
+        builder
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        min_width: Val::Px(44.0),
+                        height: Val::Px(18.0),
+                        ..default()
+                    },
+                    background_color: BackgroundColor(theme.panel_background.with_a(0.8)),
+                    ..default()
+                },
+                ViewCubeButton(view),
+            ))
+            .with_children(|builder| {
+                let mut text = builder.spawn((
+                    TextBundle::from_section(
+                        label,
+                        TextStyle {
+                            font: theme.font.clone(),
+                            font_size: 12.0,
+                            color: Color::WHITE,
+                        },
+                    ),
+                    Pickable::IGNORE,
+                ));
+                if view.is_none() {
+                    text.insert(ViewCubeProjectionText(index));
+                }
+            });
+    };
+    let row = || NodeBundle {
+        style: Style {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Row,
+            justify_content: JustifyContent::Center,
+            column_gap: Val::Px(2.4),
+            ..default()
+        },
+        ..default()
+    };
+    // an unfolded cube: top over the four sides, bottom under them
+    builder
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(6.0),
+                right: Val::Px(6.0),
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(2.4),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|builder| {
+            builder.spawn(row()).with_children(|builder| {
+                button(builder, "Top", Some(AxisView::Top));
+            });
+            builder.spawn(row()).with_children(|builder| {
+                for view in [
+                    AxisView::Left,
+                    AxisView::Front,
+                    AxisView::Right,
+                    AxisView::Back,
+                ] {
+                    button(builder, view.label(), Some(view));
+                }
+            });
+            builder.spawn(row()).with_children(|builder| {
+                button(builder, "Bottom", Some(AxisView::Bottom));
+            });
+            builder.spawn(row()).with_children(|builder| {
+                button(builder, "Persp", None);
+            });
+        });
+}
+
+/// Sends the action of a view cube button. The viewport it's in is already the active one, since
+/// the mouse is over it.
+pub fn click_view_cube(
+    mut clicks: EventReader<Pointer<Click>>,
+    buttons: Query<&ViewCubeButton>,
+    mut actions: EventWriter<MenuAction>,
+) {
+    for click in clicks.read() {
+        if click.button != PointerButton::Primary {
+            continue;
+        }
+        if let Ok(ViewCubeButton(view)) = buttons.get(click.target) {
+            actions.send(match view {
+                Some(view) => MenuAction::SetView(*view),
+                None => MenuAction::ToggleOrthographic,
+            });
+        }
+    }
+}
+
+pub fn update_view_cube(
+    cameras: Query<(&ViewportCamera, &Projection)>,
+    mut texts: Query<(&mut Text, &ViewCubeProjectionText)>,
+) {
+    for (mut text, ViewCubeProjectionText(index)) in &mut texts {
+        let Some((_, projection)) = cameras.iter().find(|(camera, _)| camera.index == *index)
+        else {
+            continue;
+        };
+        let label = match projection {
+            Projection::Perspective(_) => "Persp",
+            Projection::Orthographic(_) => "Ortho",
+        };
+        if text.sections[0].value != label {
+            text.sections[0].value = label.to_string();
+        }
+    }
+}
@@ -0,0 +1,904 @@
+//! the docking layout. the panels aren't spawned by hand anymore, they're generated from the
+//! [`DockTree`] resource and respawned whenever it changes, so rearranging the editor is just
+//! editing data. panels can also be torn off the dock into their own OS windows, see
+//! [`crate::floating`].
+use std::borrow::Cow;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::floating::canvas_offset;
+use crate::panel::PanelRegistry;
+use crate::scene::EditorOnly;
+use crate::splitter::spawn_splitter;
+use crate::tabs::{spawn_tab_bar, DockPanelBody, FloatingPanelWindow};
+use crate::theme::EditorTheme;
+
+/// Identifies a panel, like the Hierarchy or the Inspector.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PanelId(pub Cow<'static, str>);
+
+impl PanelId {
+    pub const HIERARCHY: Self = Self::from_static("hierarchy");
+    pub const VIEWPORT: Self = Self::from_static("viewport");
+    pub const INSPECTOR: Self = Self::from_static("inspector");
+    pub const FEATURES: Self = Self::from_static("features");
+    pub const ASSETS: Self = Self::from_static("assets");
+
+    pub const fn from_static(id: &'static str) -> Self {
+        Self(Cow::Borrowed(id))
+    }
+
+    pub fn new(id: impl Into<Cow<'static, str>>) -> Self {
+        Self(id.into())
+    }
+
+    /// The panel of the viewport `index`, the first one is [`PanelId::VIEWPORT`].
+    pub fn viewport(index: usize) -> Self {
+        match index {
+            0 => Self::VIEWPORT,
+            _ => Self::new(format!("viewport_{}", index + 1)),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Which way the children of a split are laid out, same as `flex-direction`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DockAxis {
+    /// side by side
+    Row,
+    /// stacked on top of each other
+    Column,
+}
+
+/// How much of a split a child takes up along the split's axis.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DockSize {
+    /// a fixed size in logical pixels
+    Px(f32),
+    /// a share of whatever space the fixed size children leave over, like `fr` in css grid
+    Fr(f32),
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DockChild {
+    pub size: DockSize,
+    /// what `size` goes back to when its splitter is double-clicked
+    pub default_size: DockSize,
+    pub node: DockNode,
+}
+
+impl DockChild {
+    pub fn new(size: DockSize, node: DockNode) -> Self {
+        Self {
+            size,
+            default_size: size,
+            node,
+        }
+    }
+
+    pub fn px(px: f32, node: DockNode) -> Self {
+        Self::new(DockSize::Px(px), node)
+    }
+
+    pub fn fr(fr: f32, node: DockNode) -> Self {
+        Self::new(DockSize::Fr(fr), node)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DockNode {
+    /// children laid out along `axis`
+    Split {
+        axis: DockAxis,
+        children: Vec<DockChild>,
+    },
+    /// a stack of panels sharing one tab bar, only `active` is shown
+    Tabs { tabs: Vec<PanelId>, active: usize },
+}
+
+impl DockNode {
+    pub fn row(children: Vec<DockChild>) -> Self {
+        Self::Split {
+            axis: DockAxis::Row,
+            children,
+        }
+    }
+
+    pub fn column(children: Vec<DockChild>) -> Self {
+        Self::Split {
+            axis: DockAxis::Column,
+            children,
+        }
+    }
+
+    pub fn tabs(tabs: Vec<PanelId>) -> Self {
+        Self::Tabs { tabs, active: 0 }
+    }
+
+    /// A tab stack with a single panel in it.
+    pub fn panel(panel: PanelId) -> Self {
+        Self::tabs(vec![panel])
+    }
+}
+
+/// How big a panel is when it's first torn off the dock, in logical pixels.
+pub const FLOATING_PANEL_SIZE: Vec2 = Vec2::new(320.0, 360.0);
+
+/// A panel that's been torn off the dock into its own window.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FloatingPanel {
+    pub panel: PanelId,
+    /// top left corner of the window, relative to the editor window's, in logical pixels
+    pub position: Vec2,
+    /// size of the window in logical pixels
+    pub size: Vec2,
+}
+
+/// The arrangement of every docked panel. Change it and the dock gets respawned.
+#[derive(Resource, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DockTree {
+    pub root: DockNode,
+    /// torn off panels, the last one was raised last
+    #[serde(default)]
+    pub floating: Vec<FloatingPanel>,
+}
+
+impl Default for DockTree {
+    /// the original layout: hierarchy and features on the left, viewport in the middle,
+    /// inspector on the right and assets along the bottom
+    fn default() -> Self {
+        Self::new(DockNode::column(vec![
+            DockChild::fr(
+                1.0,
+                DockNode::row(vec![
+                    DockChild::px(
+                        324.0,
+                        DockNode::column(vec![
+                            DockChild::fr(1.0, DockNode::panel(PanelId::HIERARCHY)),
+                            DockChild::px(240.0, DockNode::panel(PanelId::FEATURES)),
+                        ]),
+                    ),
+                    DockChild::fr(1.0, DockNode::panel(PanelId::VIEWPORT)),
+                    DockChild::px(324.0, DockNode::panel(PanelId::INSPECTOR)),
+                ]),
+            ),
+            DockChild::px(240.0, DockNode::panel(PanelId::ASSETS)),
+        ]))
+    }
+}
+
+/// One side of a tab stack, for docking a panel next to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DockEdge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+/// Where [`DockTree::move_panel`] puts a panel. Places are given relative to another panel, the
+/// `anchor`, since paths into the tree shift around while the panel is taken out.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DockTarget {
+    /// right before or after `anchor` in its tab bar
+    Tab { anchor: PanelId, after: bool },
+    /// at the end of the tab bar `anchor` is in
+    Stack { anchor: PanelId },
+    /// in a new split on one edge of the tab stack `anchor` is in
+    Edge { anchor: PanelId, edge: DockEdge },
+    /// off the dock, in a window with its top left corner at `position` in the editor window
+    Float { position: Vec2 },
+}
+
+impl DockTree {
+    /// A tree with nothing floating.
+    pub fn new(root: DockNode) -> Self {
+        Self {
+            root,
+            floating: Vec::new(),
+        }
+    }
+
+    /// Finds a node by the child indices leading to it from the root.
+    pub fn node(&self, path: &[usize]) -> Option<&DockNode> {
+        let mut node = &self.root;
+        for &index in path {
+            let DockNode::Split { children, .. } = node else {
+                return None;
+            };
+            node = &children.get(index)?.node;
+        }
+        Some(node)
+    }
+
+    /// Finds a node by the child indices leading to it from the root.
+    pub fn node_mut(&mut self, path: &[usize]) -> Option<&mut DockNode> {
+        let mut node = &mut self.root;
+        for &index in path {
+            let DockNode::Split { children, .. } = node else {
+                return None;
+            };
+            node = &mut children.get_mut(index)?.node;
+        }
+        Some(node)
+    }
+
+    /// The path to the tab stack holding `panel`, if it's docked.
+    pub fn find_panel(&self, panel: &PanelId) -> Option<Vec<usize>> {
+        fn find(node: &DockNode, panel: &PanelId, path: &mut Vec<usize>) -> bool {
+            match node {
+                DockNode::Tabs { tabs, .. } => tabs.contains(panel),
+                DockNode::Split { children, .. } => {
+                    for (index, child) in children.iter().enumerate() {
+                        path.push(index);
+                        if find(&child.node, panel, path) {
+                            return true;
+                        }
+                        path.pop();
+                    }
+                    false
+                }
+            }
+        }
+
+        let mut path = Vec::new();
+        find(&self.root, panel, &mut path).then_some(path)
+    }
+
+    /// Whether `panel` is open, docked or floating.
+    pub fn contains(&self, panel: &PanelId) -> bool {
+        self.find_panel(panel).is_some() || self.is_floating(panel)
+    }
+
+    pub fn is_floating(&self, panel: &PanelId) -> bool {
+        self.floating
+            .iter()
+            .any(|floating| floating.panel == *panel)
+    }
+
+    /// Switches the tab stack holding `panel` over to it.
+    pub fn activate(&mut self, panel: &PanelId) -> bool {
+        let Some(path) = self.find_panel(panel) else {
+            return false;
+        };
+        let Some(DockNode::Tabs { tabs, active }) = self.node_mut(&path) else {
+            return false;
+        };
+        let Some(index) = tabs.iter().position(|tab| tab == panel) else {
+            return false;
+        };
+        *active = index;
+        true
+    }
+
+    /// Shows `panel`, docking it at the end of the first tab stack if it isn't open yet.
+    pub fn open_panel(&mut self, panel: &PanelId) {
+        fn first_stack(node: &mut DockNode) -> Option<&mut DockNode> {
+            match node {
+                DockNode::Tabs { .. } => Some(node),
+                DockNode::Split { children, .. } => children
+                    .iter_mut()
+                    .find_map(|child| first_stack(&mut child.node)),
+            }
+        }
+
+        if self.activate(panel) || self.raise_floating(panel) {
+            return;
+        }
+        if let Some(DockNode::Tabs { tabs, active }) = first_stack(&mut self.root) {
+            tabs.push(panel.clone());
+            *active = tabs.len() - 1;
+        }
+    }
+
+    /// Brings the window of a floating `panel` to the front.
+    pub fn raise_floating(&mut self, panel: &PanelId) -> bool {
+        let Some(index) = self
+            .floating
+            .iter()
+            .position(|floating| floating.panel == *panel)
+        else {
+            return false;
+        };
+        let floating = self.floating.remove(index);
+        self.floating.push(floating);
+        true
+    }
+
+    /// Closes `panel`, getting rid of any tab stacks and splits that end up empty.
+    pub fn remove_panel(&mut self, panel: &PanelId) -> bool {
+        if let Some(index) = self
+            .floating
+            .iter()
+            .position(|floating| floating.panel == *panel)
+        {
+            self.floating.remove(index);
+            return true;
+        }
+        let Some(path) = self.find_panel(panel) else {
+            return false;
+        };
+        let Some(DockNode::Tabs { tabs, active }) = self.node_mut(&path) else {
+            return false;
+        };
+        let Some(index) = tabs.iter().position(|tab| tab == panel) else {
+            return false;
+        };
+        tabs.remove(index);
+        if index < *active {
+            *active -= 1;
+        }
+        *active = (*active).min(tabs.len().saturating_sub(1));
+        self.normalize();
+        true
+    }
+
+    /// Moves an open `panel` somewhere else. Returns `false` if it didn't go anywhere.
+    pub fn move_panel(&mut self, panel: &PanelId, target: DockTarget) -> bool {
+        let target = match target {
+            DockTarget::Float { position } => return self.float_panel(panel, position),
+            // it's already there
+            DockTarget::Tab { anchor, .. } | DockTarget::Stack { anchor } if anchor == *panel => {
+                return false
+            }
+            // docking a panel onto the edge of its own stack means docking it next to whatever
+            // it shares the stack with
+            DockTarget::Edge { anchor, edge } if anchor == *panel => {
+                let Some(DockNode::Tabs { tabs, .. }) =
+                    self.find_panel(panel).and_then(|path| self.node(&path))
+                else {
+                    return false;
+                };
+                let Some(other) = tabs.iter().find(|tab| *tab != panel) else {
+                    return false;
+                };
+                DockTarget::Edge {
+                    anchor: other.clone(),
+                    edge,
+                }
+            }
+            target => target,
+        };
+
+        let (DockTarget::Tab { anchor, .. }
+        | DockTarget::Stack { anchor }
+        | DockTarget::Edge { anchor, .. }) = &target
+        else {
+            return false;
+        };
+        // panels can only be docked next to other docked panels
+        if self.find_panel(anchor).is_none() || !self.remove_panel(panel) {
+            return false;
+        }
+        let Some(path) = self.find_panel(anchor) else {
+            return false;
+        };
+        let Some(node) = self.node_mut(&path) else {
+            return false;
+        };
+
+        match target {
+            DockTarget::Tab { anchor, after } => {
+                let DockNode::Tabs { tabs, active } = node else {
+                    return false;
+                };
+                let index =
+                    tabs.iter().position(|tab| *tab == anchor).unwrap_or(0) + after as usize;
+                tabs.insert(index, panel.clone());
+                *active = index;
+            }
+            DockTarget::Stack { .. } => {
+                let DockNode::Tabs { tabs, active } = node else {
+                    return false;
+                };
+                tabs.push(panel.clone());
+                *active = tabs.len() - 1;
+            }
+            DockTarget::Edge { edge, .. } => {
+                let existing = std::mem::replace(node, DockNode::tabs(Vec::new()));
+                let new = DockNode::panel(panel.clone());
+                *node = match edge {
+                    DockEdge::Left => {
+                        DockNode::row(vec![DockChild::fr(1.0, new), DockChild::fr(1.0, existing)])
+                    }
+                    DockEdge::Right => {
+                        DockNode::row(vec![DockChild::fr(1.0, existing), DockChild::fr(1.0, new)])
+                    }
+                    DockEdge::Top => DockNode::column(vec![
+                        DockChild::fr(1.0, new),
+                        DockChild::fr(1.0, existing),
+                    ]),
+                    DockEdge::Bottom => DockNode::column(vec![
+                        DockChild::fr(1.0, existing),
+                        DockChild::fr(1.0, new),
+                    ]),
+                };
+            }
+            DockTarget::Float { .. } => unreachable!(),
+        }
+        true
+    }
+
+    /// Tears `panel` off the dock into a floating window at `position`, or moves it there if it's
+    /// already floating.
+    pub fn float_panel(&mut self, panel: &PanelId, position: Vec2) -> bool {
+        if let Some(index) = self
+            .floating
+            .iter()
+            .position(|floating| floating.panel == *panel)
+        {
+            let mut floating = self.floating.remove(index);
+            floating.position = position;
+            self.floating.push(floating);
+            return true;
+        }
+        if !self.remove_panel(panel) {
+            return false;
+        }
+        self.floating.push(FloatingPanel {
+            panel: panel.clone(),
+            position,
+            size: FLOATING_PANEL_SIZE,
+        });
+        true
+    }
+
+    /// Docks a floating `panel` back at the end of the first tab stack.
+    pub fn dock_floating(&mut self, panel: &PanelId) -> bool {
+        if !self.is_floating(panel) {
+            return false;
+        }
+        self.remove_panel(panel);
+        self.open_panel(panel);
+        true
+    }
+
+    /// Closes every panel `keep` returns `false` for.
+    pub fn retain_panels(&mut self, mut keep: impl FnMut(&PanelId) -> bool) {
+        fn retain(node: &mut DockNode, keep: &mut impl FnMut(&PanelId) -> bool) {
+            match node {
+                DockNode::Tabs { tabs, active } => {
+                    tabs.retain(|tab| keep(tab));
+                    *active = (*active).min(tabs.len().saturating_sub(1));
+                }
+                DockNode::Split { children, .. } => {
+                    for child in children {
+                        retain(&mut child.node, keep);
+                    }
+                }
+            }
+        }
+
+        retain(&mut self.root, &mut keep);
+        self.floating.retain(|floating| keep(&floating.panel));
+        self.normalize();
+    }
+
+    /// Whether there's no panel open at all.
+    pub fn is_empty(&self) -> bool {
+        matches!(&self.root, DockNode::Tabs { tabs, .. } if tabs.is_empty())
+            && self.floating.is_empty()
+    }
+
+    /// Drops empty tab stacks and splits, and replaces splits with only one child by that child.
+    pub fn normalize(&mut self) {
+        fn normalize(node: &mut DockNode) -> bool {
+            match node {
+                DockNode::Tabs { tabs, .. } => !tabs.is_empty(),
+                DockNode::Split { children, .. } => {
+                    children.retain_mut(|child| normalize(&mut child.node));
+                    match children.len() {
+                        0 => return false,
+                        1 => *node = children.remove(0).node,
+                        _ => {}
+                    }
+                    true
+                }
+            }
+        }
+
+        if !normalize(&mut self.root) {
+            self.root = DockNode::tabs(Vec::new());
+        }
+    }
+}
+
+/// The node the [`DockTree`] gets spawned into.
+#[derive(Component)]
+pub struct DockRoot;
+
+/// What spawning the dock needs on hand.
+pub(crate) struct DockSpawner<'a> {
+    pub theme: &'a EditorTheme,
+    pub panels: &'a PanelRegistry,
+    pub asset_server: &'a AssetServer,
+}
+
+pub fn rebuild_dock(
+    mut commands: Commands,
+    tree: Res<DockTree>,
+    roots: Query<Entity, With<DockRoot>>,
+    floating_roots: Query<Entity, With<FloatingPanelWindow>>,
+    theme: Res<EditorTheme>,
+    panels: Res<PanelRegistry>,
+    asset_server: Res<AssetServer>,
+    ui_scale: Res<UiScale>,
+) {
+    let spawner = DockSpawner {
+        theme: &theme,
+        panels: &panels,
+        asset_server: &asset_server,
+    };
+    for root in &roots {
+        commands
+            .entity(root)
+            .despawn_descendants()
+            .with_children(|builder| {
+                spawn_dock_node(
+                    builder,
+                    &tree.root,
+                    &mut Vec::new(),
+                    DockSize::Fr(1.0),
+                    DockAxis::Row,
+                    &spawner,
+                );
+            });
+    }
+    for root in &floating_roots {
+        commands.entity(root).despawn_recursive();
+    }
+    for (index, floating) in tree.floating.iter().enumerate() {
+        spawn_floating_panel(
+            &mut commands,
+            floating,
+            canvas_offset(index),
+            ui_scale.0 as f32,
+            &spawner,
+        );
+    }
+}
+
+/// Sizes a child of a split along the split's axis, and stretches it across the other one.
+pub(crate) fn apply_dock_size(style: &mut Style, size: DockSize, parent_axis: DockAxis) {
+    let (main, cross, min_main) = match parent_axis {
+        DockAxis::Row => (&mut style.width, &mut style.height, &mut style.min_width),
+        DockAxis::Column => (&mut style.height, &mut style.width, &mut style.min_height),
+    };
+    *cross = Val::Percent(100.0);
+    // without this flex items refuse to shrink below their content
+    *min_main = Val::Px(0.0);
+    match size {
+        DockSize::Px(px) => {
+            *main = Val::Px(px);
+            style.flex_basis = Val::Auto;
+            style.flex_grow = 0.0;
+            style.flex_shrink = 0.0;
+        }
+        DockSize::Fr(fr) => {
+            *main = Val::Auto;
+            style.flex_basis = Val::Px(0.0);
+            style.flex_grow = fr;
+            style.flex_shrink = 1.0;
+        }
+    }
+}
+
+fn spawn_dock_node(
+    builder: &mut ChildBuilder,
+    node: &DockNode,
+    path: &mut Vec<usize>,
+    size: DockSize,
+    parent_axis: DockAxis,
+    spawner: &DockSpawner,
+) {
+    match node {
+        DockNode::Split { axis, children } => {
+            let mut style = Style {
+                display: Display::Flex,
+                flex_direction: match axis {
+                    DockAxis::Row => FlexDirection::Row,
+                    DockAxis::Column => FlexDirection::Column,
+                },
+                ..default()
+            };
+            apply_dock_size(&mut style, size, parent_axis);
+            builder
+                .spawn(NodeBundle { style, ..default() })
+                .with_children(|builder| {
+                    for (index, child) in children.iter().enumerate() {
+                        // the splitters take the place of the 6px gap between panels
+                        if index > 0 {
+                            spawn_splitter(builder, path.clone(), index - 1, *axis);
+                        }
+                        path.push(index);
+                        spawn_dock_node(builder, &child.node, path, child.size, *axis, spawner);
+                        path.pop();
+                    }
+                });
+        }
+        DockNode::Tabs { tabs, active } => {
+            spawn_tab_stack(builder, tabs, *active, size, parent_axis, spawner);
+        }
+    }
+}
+
+fn spawn_tab_stack(
+    builder: &mut ChildBuilder,
+    tabs: &[PanelId],
+    active: usize,
+    size: DockSize,
+    parent_axis: DockAxis,
+    spawner: &DockSpawner,
+) {
+    let theme = spawner.theme;
+    let Some(active_panel) = tabs.get(active).or(tabs.first()) else {
+        return;
+    };
+
+    // the tabs sit in the 26.4px above the panel body
+    let mut style = Style {
+        display: Display::Flex,
+        flex_direction: FlexDirection::Column,
+        position_type: PositionType::Relative,
+        padding: UiRect::top(Val::Px(26.4)),
+        ..default()
+    };
+    apply_dock_size(&mut style, size, parent_axis);
+    builder
+        .spawn(NodeBundle { style, ..default() })
+        .with_children(|builder| {
+            // panel body
+            builder
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            display: Display::Flex,
+                            padding: UiRect::all(Val::Px(6.0)),
+                            flex_direction: FlexDirection::Column,
+                            position_type: PositionType::Relative,
+                            flex_grow: 1.0,
+                            min_height: Val::Px(0.0),
+                            overflow: Overflow::clip(),
+                            ..default()
+                        },
+                        background_color: BackgroundColor(theme.panel_background),
+                        ..default()
+                    },
+                    DockPanelBody {
+                        panel: active_panel.clone(),
+                    },
+                ))
+                .with_children(|builder| {
+                    if let Some(panel) = spawner.panels.get(active_panel) {
+                        panel.build(builder, theme);
+                    }
+                });
+
+            // tab list, spawned after the body so it draws on top of it
+            spawn_tab_bar(builder, tabs, active_panel, spawner);
+        });
+}
+
+/// Spawns the UI of a floating panel where its window's camera looks, at `offset`.
+fn spawn_floating_panel(
+    commands: &mut Commands,
+    floating: &FloatingPanel,
+    offset: Vec2,
+    ui_scale: f32,
+    spawner: &DockSpawner,
+) {
+    let theme = spawner.theme;
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Column,
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(offset.x),
+                    top: Val::Px(offset.y),
+                    width: Val::Px(floating.size.x / ui_scale),
+                    height: Val::Px(floating.size.y / ui_scale),
+                    padding: UiRect {
+                        left: Val::Px(3.0),
+                        right: Val::Px(3.0),
+                        top: Val::Px(29.4),
+                        bottom: Val::Px(3.0),
+                    },
+                    ..default()
+                },
+                background_color: BackgroundColor(theme.window_background),
+                ..default()
+            },
+            EditorOnly,
+            FloatingPanelWindow {
+                panel: floating.panel.clone(),
+            },
+        ))
+        .with_children(|builder| {
+            builder
+                .spawn(NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        padding: UiRect::all(Val::Px(6.0)),
+                        flex_direction: FlexDirection::Column,
+                        position_type: PositionType::Relative,
+                        flex_grow: 1.0,
+                        min_height: Val::Px(0.0),
+                        overflow: Overflow::clip(),
+                        ..default()
+                    },
+                    background_color: BackgroundColor(theme.panel_background),
+                    ..default()
+                })
+                .with_children(|builder| {
+                    if let Some(panel) = spawner.panels.get(&floating.panel) {
+                        panel.build(builder, theme);
+                    }
+                });
+
+            spawn_tab_bar(
+                builder,
+                std::slice::from_ref(&floating.panel),
+                &floating.panel,
+                spawner,
+            );
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(id: &'static str) -> PanelId {
+        PanelId::from_static(id)
+    }
+
+    /// `a` and `b` stacked in one tab bar, next to `c`.
+    fn tree() -> DockTree {
+        DockTree::new(DockNode::row(vec![
+            DockChild::fr(1.0, DockNode::tabs(vec![id("a"), id("b")])),
+            DockChild::px(200.0, DockNode::panel(id("c"))),
+        ]))
+    }
+
+    #[test]
+    fn move_onto_edge_of_own_stack_splits_it() {
+        let mut tree = tree();
+        let target = DockTarget::Edge {
+            anchor: id("a"),
+            edge: DockEdge::Left,
+        };
+        assert!(tree.move_panel(&id("a"), target));
+        assert_eq!(
+            tree.root,
+            DockNode::row(vec![
+                DockChild::fr(
+                    1.0,
+                    DockNode::row(vec![
+                        DockChild::fr(1.0, DockNode::panel(id("a"))),
+                        DockChild::fr(1.0, DockNode::panel(id("b"))),
+                    ])
+                ),
+                DockChild::px(200.0, DockNode::panel(id("c"))),
+            ])
+        );
+    }
+
+    #[test]
+    fn move_onto_edge_of_own_stack_alone_does_nothing() {
+        let mut tree = tree();
+        let before = tree.clone();
+        let target = DockTarget::Edge {
+            anchor: id("c"),
+            edge: DockEdge::Top,
+        };
+        assert!(!tree.move_panel(&id("c"), target));
+        assert_eq!(tree, before);
+    }
+
+    #[test]
+    fn move_into_own_tab_bar_does_nothing() {
+        let mut tree = tree();
+        let before = tree.clone();
+        assert!(!tree.move_panel(&id("a"), DockTarget::Stack { anchor: id("a") }));
+        assert_eq!(tree, before);
+    }
+
+    #[test]
+    fn move_next_to_tab() {
+        let mut tree = tree();
+        let target = DockTarget::Tab {
+            anchor: id("a"),
+            after: false,
+        };
+        assert!(tree.move_panel(&id("c"), target));
+        assert_eq!(
+            tree.root,
+            DockNode::Tabs {
+                tabs: vec![id("c"), id("a"), id("b")],
+                active: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn normalize_collapses_single_child_splits() {
+        let mut tree = DockTree::new(DockNode::column(vec![DockChild::fr(
+            1.0,
+            DockNode::row(vec![
+                DockChild::fr(1.0, DockNode::tabs(Vec::new())),
+                DockChild::fr(1.0, DockNode::panel(id("a"))),
+            ]),
+        )]));
+        tree.normalize();
+        assert_eq!(tree.root, DockNode::panel(id("a")));
+    }
+
+    #[test]
+    fn remove_panel_keeps_active_tab() {
+        let mut tree = DockTree::new(DockNode::Tabs {
+            tabs: vec![id("a"), id("b"), id("c")],
+            active: 2,
+        });
+        assert!(tree.remove_panel(&id("a")));
+        assert_eq!(
+            tree.root,
+            DockNode::Tabs {
+                tabs: vec![id("b"), id("c")],
+                active: 1,
+            }
+        );
+        assert!(!tree.remove_panel(&id("a")));
+    }
+
+    #[test]
+    fn remove_last_panel_leaves_empty_tree() {
+        let mut tree = tree();
+        assert!(tree.remove_panel(&id("a")));
+        assert!(tree.remove_panel(&id("c")));
+        assert!(!tree.is_empty());
+        assert!(tree.remove_panel(&id("b")));
+        assert!(tree.is_empty());
+        assert_eq!(tree.root, DockNode::tabs(Vec::new()));
+    }
+
+    #[test]
+    fn retain_panels_drops_unknown_panels() {
+        let mut tree = tree();
+        tree.float_panel(&id("b"), Vec2::new(10.0, 20.0));
+        tree.retain_panels(|panel| *panel == id("c"));
+        assert_eq!(tree.root, DockNode::panel(id("c")));
+        assert!(tree.floating.is_empty());
+    }
+
+    #[test]
+    fn dock_floating_docks_into_first_stack() {
+        let mut tree = tree();
+        tree.float_panel(&id("c"), Vec2::ZERO);
+        assert!(tree.dock_floating(&id("c")));
+        assert!(tree.floating.is_empty());
+        assert_eq!(
+            tree.root,
+            DockNode::Tabs {
+                tabs: vec![id("a"), id("b"), id("c")],
+                active: 2,
+            }
+        );
+        assert!(!tree.dock_floating(&id("c")));
+    }
+
+    #[test]
+    fn dock_floating_with_nothing_docked() {
+        let mut tree = DockTree::new(DockNode::panel(id("a")));
+        tree.float_panel(&id("a"), Vec2::ZERO);
+        assert!(tree.dock_floating(&id("a")));
+        assert_eq!(tree.root, DockNode::panel(id("a")));
+    }
+}
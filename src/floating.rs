@@ -0,0 +1,516 @@
+//! floating panels in their own OS windows. bevy_ui only lays out nodes against the primary
+//! window, so the UI of each floating panel is laid out far off to the right of the editor
+//! window, and the floating window's camera looks at just that part. picking goes the other way:
+//! pointers on a floating window get moved over to where its UI is before hit testing.
+//! resizing or moving a floating window is saved in the [`DockTree`], closing it docks its panel
+//! back.
+use bevy::core_pipeline::clear_color::ClearColorConfig;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::render::camera::{NormalizedRenderTarget, RenderTarget};
+use bevy::render::view::{ExtractedView, RenderLayers};
+use bevy::render::{Extract, Render, RenderApp, RenderSet};
+use bevy::ui::{CalculatedClip, DefaultCameraView, UiStack};
+use bevy::window::{
+    PrimaryWindow, WindowCloseRequested, WindowMoved, WindowRef, WindowResized, WindowResolution,
+};
+use bevy_mod_picking::backend::prelude::*;
+use bevy_mod_picking::pointer::Location;
+
+use crate::dock::{DockTree, FloatingPanel, PanelId};
+use crate::panel::PanelRegistry;
+use crate::scene::EditorOnly;
+use crate::tabs::FloatingPanelWindow;
+use crate::theme::EditorTheme;
+
+/// Where the UI of the first floating window is laid out, in ui pixels. Far enough to the right
+/// that the editor window never shows it.
+const CANVAS_START: f32 = 100_000.0;
+
+/// How far apart the UI of each floating window is laid out, wider than any window gets.
+const CANVAS_STRIDE: f32 = 20_000.0;
+
+/// Where the UI of the floating panel `index` in [`DockTree::floating`] is laid out.
+pub fn canvas_offset(index: usize) -> Vec2 {
+    Vec2::new(CANVAS_START + index as f32 * CANVAS_STRIDE, 0.0)
+}
+
+/// An OS window showing a floating panel.
+#[derive(Component, Debug)]
+pub struct FloatingWindow {
+    pub panel: PanelId,
+    /// where its UI is laid out, see [`canvas_offset`]
+    pub offset: Vec2,
+    pub camera: Entity,
+}
+
+/// The camera drawing the UI of a [`FloatingWindow`].
+#[derive(Component, Debug)]
+pub struct FloatingWindowCamera {
+    /// where the UI it shows is laid out
+    pub offset: Vec2,
+}
+
+/// [`FloatingWindowCamera::offset`] in the render world.
+#[derive(Component)]
+struct ExtractedCanvasOffset(Vec2);
+
+/// Moves the UI view of each floating window's camera over to its panel's UI.
+pub(crate) fn add_render_systems(app: &mut App) {
+    let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+        return;
+    };
+    render_app
+        .add_systems(ExtractSchedule, extract_canvas_offsets)
+        .add_systems(Render, offset_ui_views.in_set(RenderSet::ManageViews));
+}
+
+fn extract_canvas_offsets(
+    mut commands: Commands,
+    cameras: Extract<Query<(Entity, &FloatingWindowCamera)>>,
+) {
+    for (entity, camera) in &cameras {
+        commands
+            .get_or_spawn(entity)
+            .insert(ExtractedCanvasOffset(camera.offset));
+    }
+}
+
+/// bevy_ui always looks at the top left corner of the layout, shift it to the panel's UI.
+fn offset_ui_views(
+    cameras: Query<(&DefaultCameraView, &ExtractedCanvasOffset)>,
+    mut views: Query<&mut ExtractedView>,
+) {
+    for (default_view, offset) in &cameras {
+        if let Ok(mut view) = views.get_mut(default_view.0) {
+            let z = view.transform.translation().z;
+            view.transform = GlobalTransform::from_xyz(offset.0.x, offset.0.y, z);
+        }
+    }
+}
+
+/// Where pointers are, for everything that compares them against the rects of nodes.
+#[derive(SystemParam)]
+pub struct PointerWindows<'w, 's> {
+    primary: Query<'w, 's, (Entity, &'static Window), With<PrimaryWindow>>,
+    floating: Query<'w, 's, (&'static Window, &'static FloatingWindow)>,
+    ui_scale: Res<'w, UiScale>,
+}
+
+impl PointerWindows<'_, '_> {
+    /// Where a pointer is, lined up with the UI of the window it's over.
+    pub fn position(&self, location: &Location) -> Vec2 {
+        let offset = self
+            .floating(location)
+            .map_or(Vec2::ZERO, |(_, floating)| floating.offset);
+        location.position + offset * self.ui_scale.0 as f32
+    }
+
+    /// Where a pointer is relative to the editor window, in its logical pixels, even while it's
+    /// over a floating window. `None` if the OS hasn't said where the windows are.
+    pub fn editor_position(&self, location: &Location) -> Option<Vec2> {
+        let NormalizedRenderTarget::Window(window) = location.target else {
+            return None;
+        };
+        let (primary, primary_window) = self.primary.get_single().ok()?;
+        if window.entity() == primary {
+            return Some(location.position);
+        }
+        let (floating_window, _) = self.floating(location)?;
+        let (WindowPosition::At(from), WindowPosition::At(to)) =
+            (floating_window.position, primary_window.position)
+        else {
+            return None;
+        };
+        let physical =
+            (from - to).as_vec2() + location.position * floating_window.scale_factor() as f32;
+        Some(physical / primary_window.scale_factor() as f32)
+    }
+
+    /// The size of the editor window in logical pixels.
+    pub fn editor_size(&self) -> Option<Vec2> {
+        let (_, window) = self.primary.get_single().ok()?;
+        Some(Vec2::new(window.width(), window.height()))
+    }
+
+    fn floating(&self, location: &Location) -> Option<(&Window, &FloatingWindow)> {
+        match location.target {
+            NormalizedRenderTarget::Window(window) => self.floating.get(window.entity()).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// Finds which window a node shows up in.
+#[derive(SystemParam)]
+pub struct NodeWindows<'w, 's> {
+    primary: Query<'w, 's, Entity, With<PrimaryWindow>>,
+    parents: Query<'w, 's, &'static Parent>,
+    panels: Query<'w, 's, &'static FloatingPanelWindow>,
+    windows: Query<'w, 's, (Entity, &'static FloatingWindow)>,
+}
+
+impl NodeWindows<'_, '_> {
+    /// The window `node` shows up in and where its UI is laid out, which is the top left corner
+    /// for the editor window.
+    pub fn get(&self, node: Entity) -> Option<(Entity, Vec2)> {
+        let mut entity = node;
+        let panel = loop {
+            if let Ok(root) = self.panels.get(entity) {
+                break &root.panel;
+            }
+            match self.parents.get(entity) {
+                Ok(parent) => entity = parent.get(),
+                Err(_) => return Some((self.primary.get_single().ok()?, Vec2::ZERO)),
+            }
+        };
+        self.windows
+            .iter()
+            .find(|(_, window)| window.panel == *panel)
+            .map(|(entity, window)| (entity, window.offset))
+    }
+
+    /// Whether `window` is the editor window.
+    pub fn is_primary(&self, window: Entity) -> bool {
+        self.primary.contains(window)
+    }
+}
+
+/// Opens a window for every floating panel and closes the ones whose panel isn't floating anymore.
+/// Closing a floating window docks its panel back.
+pub fn sync_floating_windows(
+    mut commands: Commands,
+    mut tree: ResMut<DockTree>,
+    panels: Res<PanelRegistry>,
+    theme: Res<EditorTheme>,
+    mut close_requests: EventReader<WindowCloseRequested>,
+    primary: Query<&Window, (With<PrimaryWindow>, Without<FloatingWindow>)>,
+    mut windows: Query<(Entity, &mut Window, &mut FloatingWindow)>,
+    mut cameras: Query<&mut FloatingWindowCamera>,
+    // the panel that was last on top, to focus its window when it's raised
+    mut top: Local<Option<PanelId>>,
+) {
+    let Ok(primary) = primary.get_single() else {
+        // the editor is closing. close the floating windows too so the app can exit, but keep
+        // them in the tree so they open again next time
+        for (entity, _, floating) in &windows {
+            commands.entity(entity).despawn();
+            commands.entity(floating.camera).despawn();
+        }
+        close_requests.clear();
+        return;
+    };
+
+    // bevy despawns the window itself
+    let mut closed = Vec::new();
+    for request in close_requests.read() {
+        if let Ok((_, _, floating)) = windows.get(request.window) {
+            tree.dock_floating(&floating.panel);
+            commands.entity(floating.camera).despawn();
+            closed.push(request.window);
+        }
+    }
+    if !tree.is_changed() {
+        return;
+    }
+
+    for (index, floating) in tree.floating.iter().enumerate() {
+        let offset = canvas_offset(index);
+        let title = panels.title(&floating.panel);
+        let existing = windows
+            .iter_mut()
+            .find(|(_, _, window)| window.panel == floating.panel);
+        let Some((_, mut window, mut floating_window)) = existing else {
+            spawn_floating_window(&mut commands, floating, title, offset, primary, &theme);
+            continue;
+        };
+        if window.title != title {
+            window.title = title.to_string();
+        }
+        if floating_window.offset != offset {
+            floating_window.offset = offset;
+            if let Ok(mut camera) = cameras.get_mut(floating_window.camera) {
+                camera.offset = offset;
+            }
+        }
+    }
+    for (entity, _, floating) in &windows {
+        if !tree.is_floating(&floating.panel) && !closed.contains(&entity) {
+            commands.entity(entity).despawn();
+            commands.entity(floating.camera).despawn();
+        }
+    }
+
+    let raised = tree.floating.last().map(|floating| floating.panel.clone());
+    if raised != *top {
+        if let Some((_, mut window, _)) = windows
+            .iter_mut()
+            .find(|(_, _, window)| Some(&window.panel) == raised.as_ref())
+        {
+            window.focused = true;
+        }
+        *top = raised;
+    }
+}
+
+fn spawn_floating_window(
+    commands: &mut Commands,
+    floating: &FloatingPanel,
+    title: &str,
+    offset: Vec2,
+    primary: &Window,
+    theme: &EditorTheme,
+) {
+    // positions are relative to the editor window, the OS wants them in physical pixels
+    let position = match primary.position {
+        WindowPosition::At(origin) => WindowPosition::At(
+            origin + (floating.position * primary.scale_factor() as f32).as_ivec2(),
+        ),
+        _ => WindowPosition::Automatic,
+    };
+    let window = commands
+        .spawn((
+            Window {
+                title: title.to_string(),
+                resolution: WindowResolution::new(floating.size.x, floating.size.y),
+                position,
+                ..default()
+            },
+            Name::new(format!("{title} Window")),
+            EditorOnly,
+        ))
+        .id();
+    let camera = commands
+        .spawn((
+            Camera2dBundle {
+                camera: Camera {
+                    target: RenderTarget::Window(WindowRef::Entity(window)),
+                    ..default()
+                },
+                camera_2d: Camera2d {
+                    clear_color: ClearColorConfig::Custom(theme.window_background),
+                },
+                ..default()
+            },
+            // only the UI, not the scene
+            RenderLayers::none(),
+            FloatingWindowCamera { offset },
+            Name::new(format!("{title} Window Camera")),
+            EditorOnly,
+        ))
+        .id();
+    commands.entity(window).insert(FloatingWindow {
+        panel: floating.panel.clone(),
+        offset,
+        camera,
+    });
+}
+
+/// Keeps the [`DockTree`] up to date as floating windows get resized and moved, without
+/// respawning the dock.
+pub fn follow_floating_windows(
+    mut resizes: EventReader<WindowResized>,
+    mut moves: EventReader<WindowMoved>,
+    mut tree: ResMut<DockTree>,
+    primary: Query<&Window, With<PrimaryWindow>>,
+    windows: Query<(&Window, &FloatingWindow)>,
+    mut roots: Query<(&FloatingPanelWindow, &mut Style)>,
+    ui_scale: Res<UiScale>,
+) {
+    let tree = tree.bypass_change_detection();
+    for resize in resizes.read() {
+        let Ok((_, window)) = windows.get(resize.window) else {
+            continue;
+        };
+        let size = Vec2::new(resize.width, resize.height);
+        if let Some(floating) = tree
+            .floating
+            .iter_mut()
+            .find(|floating| floating.panel == window.panel)
+        {
+            floating.size = size;
+        }
+        for (root, mut style) in &mut roots {
+            if root.panel == window.panel {
+                style.width = Val::Px(size.x / ui_scale.0 as f32);
+                style.height = Val::Px(size.y / ui_scale.0 as f32);
+            }
+        }
+    }
+
+    if moves.read().count() == 0 {
+        return;
+    }
+    let Ok(primary) = primary.get_single() else {
+        return;
+    };
+    let WindowPosition::At(origin) = primary.position else {
+        return;
+    };
+    for (window, floating_window) in &windows {
+        let WindowPosition::At(position) = window.position else {
+            continue;
+        };
+        if let Some(floating) = tree
+            .floating
+            .iter_mut()
+            .find(|floating| floating.panel == floating_window.panel)
+        {
+            floating.position = (position - origin).as_vec2() / primary.scale_factor() as f32;
+        }
+    }
+}
+
+/// Picks the UI of floating windows, like bevy_mod_picking's backend does for the primary window.
+pub fn pick_floating_windows(
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    windows: Query<&FloatingWindow>,
+    cameras: Query<&Camera>,
+    ui_stack: Res<UiStack>,
+    nodes: Query<(
+        &Node,
+        &GlobalTransform,
+        Option<&Pickable>,
+        Option<&CalculatedClip>,
+        Option<&ViewVisibility>,
+    )>,
+    ui_scale: Res<UiScale>,
+    mut output: EventWriter<PointerHits>,
+) {
+    for (pointer, location) in &pointers {
+        let Some(location) = location.location() else {
+            continue;
+        };
+        let NormalizedRenderTarget::Window(window) = location.target else {
+            continue;
+        };
+        let Ok(floating) = windows.get(window.entity()) else {
+            continue;
+        };
+        let Ok(camera) = cameras.get(floating.camera) else {
+            continue;
+        };
+        let position = location.position / ui_scale.0 as f32 + floating.offset;
+
+        // topmost first, until something blocks what's under it
+        let mut picks = Vec::new();
+        let mut depth = 0.0;
+        for entity in ui_stack.uinodes.iter().rev() {
+            let Ok((node, transform, pickable, clip, visibility)) = nodes.get(*entity) else {
+                continue;
+            };
+            if visibility.is_some_and(|visibility| !visibility.get()) {
+                continue;
+            }
+            let mut rect = node.logical_rect(transform);
+            if let Some(clip) = clip {
+                rect = rect.intersect(clip.clip);
+            }
+            if !rect.contains(position) {
+                continue;
+            }
+            picks.push((*entity, HitData::new(floating.camera, depth, None, None)));
+            if pickable.is_none_or(|pickable| pickable.should_block_lower) {
+                break;
+            }
+            depth += 0.00001;
+        }
+        output.send(PointerHits::new(*pointer, picks, camera.order as f32 + 0.5));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dock::{DockNode, FLOATING_PANEL_SIZE};
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_event::<WindowCloseRequested>()
+            .insert_resource(EditorTheme {
+                font: Handle::default(),
+                icon: Handle::default(),
+                window_background: Color::GRAY,
+                panel_background: Color::GRAY,
+                viewport_background: Color::GRAY,
+                input_background: Color::GRAY,
+                accent: Color::BLUE,
+                error: Color::RED,
+            })
+            .init_resource::<PanelRegistry>()
+            .insert_resource(DockTree::new(DockNode::tabs(vec![PanelId::HIERARCHY])))
+            .add_systems(Update, sync_floating_windows);
+        app.world.spawn((Window::default(), PrimaryWindow));
+        app
+    }
+
+    fn floating_windows(app: &mut App) -> Vec<(PanelId, Vec2)> {
+        app.world
+            .query::<&FloatingWindow>()
+            .iter(&app.world)
+            .map(|window| (window.panel.clone(), window.offset))
+            .collect()
+    }
+
+    #[test]
+    fn canvases_are_out_of_the_editor_window_and_apart() {
+        assert!(canvas_offset(0).x > 10_000.0);
+        assert!(canvas_offset(1).x - canvas_offset(0).x > 10_000.0);
+    }
+
+    #[test]
+    fn floating_panels_get_windows() {
+        let mut app = app();
+        app.world
+            .resource_mut::<DockTree>()
+            .float_panel(&PanelId::HIERARCHY, Vec2::new(40.0, 30.0));
+        app.update();
+
+        assert_eq!(
+            floating_windows(&mut app),
+            [(PanelId::HIERARCHY, canvas_offset(0))]
+        );
+        let (window_entity, window, floating) = app
+            .world
+            .query::<(Entity, &Window, &FloatingWindow)>()
+            .single(&app.world);
+        assert_eq!(window.width(), FLOATING_PANEL_SIZE.x);
+        let camera = app.world.get::<Camera>(floating.camera).unwrap();
+        assert!(matches!(
+            camera.target,
+            RenderTarget::Window(WindowRef::Entity(target)) if target == window_entity
+        ));
+
+        app.world
+            .resource_mut::<DockTree>()
+            .dock_floating(&PanelId::HIERARCHY);
+        app.update();
+        assert!(floating_windows(&mut app).is_empty());
+        assert!(app
+            .world
+            .query::<&FloatingWindowCamera>()
+            .iter(&app.world)
+            .next()
+            .is_none());
+    }
+
+    #[test]
+    fn closing_a_floating_window_docks_its_panel() {
+        let mut app = app();
+        app.world
+            .resource_mut::<DockTree>()
+            .float_panel(&PanelId::HIERARCHY, Vec2::ZERO);
+        app.update();
+
+        let window = app
+            .world
+            .query_filtered::<Entity, With<FloatingWindow>>()
+            .single(&app.world);
+        app.world.send_event(WindowCloseRequested { window });
+        app.update();
+
+        let tree = app.world.resource::<DockTree>();
+        assert!(tree.floating.is_empty());
+        assert_eq!(tree.find_panel(&PanelId::HIERARCHY), Some(Vec::new()));
+    }
+}
@@ -0,0 +1,919 @@
+//! the hierarchy panel. it lists the scene's entities as a tree following `Parent`/`Children`, and
+//! keeps up with the world as things get spawned, despawned, renamed and reparented, without
+//! respawning the whole list every time. rows can be dragged onto each other to reparent, and the
+//! search field on top filters them, see [`SearchQuery`] for what it takes.
+use std::ops::Range;
+
+use bevy::ecs::component::Components;
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+use bevy_mod_picking::prelude::*;
+
+use crate::dock::PanelId;
+use crate::floating::PointerWindows;
+use crate::menu::MenuAction;
+use crate::scene::ShowInternals;
+use crate::search::SearchQuery;
+use crate::selection::Selection;
+use crate::tabs::{DockPanelBody, DropPreview};
+use crate::theme::EditorTheme;
+use crate::widgets::text_input::{
+    spawn_text_input, TextInput, TextInputCancelled, TextInputChanged,
+};
+
+/// How close together two clicks on a row have to be to frame its entity, in seconds.
+const DOUBLE_CLICK_TIME: f32 = 0.4;
+
+/// What the hierarchy can list: everything that isn't UI, a window or a picking pointer. UI is left
+/// out even with [`ShowInternals`] on, the hierarchy's own rows would be in it. Which of these
+/// actually get listed is up to [`ShowInternals::shows`].
+pub type SceneFilter = (Without<Node>, Without<Window>, Without<PointerId>);
+
+/// The node the hierarchy's rows are spawned into.
+#[derive(Component, Default)]
+pub struct HierarchyList {
+    rows: HashMap<Entity, HierarchyRowNodes>,
+    /// collapsed rows that got expanded to show a search match, see [`reveal_hierarchy_matches`]
+    revealed: Vec<Entity>,
+}
+
+/// The UI nodes making up one entity's row.
+#[derive(Clone, Copy, Debug)]
+struct HierarchyRowNodes {
+    row: Entity,
+    chevron: Entity,
+    label: Entity,
+    /// where the rows of the entity's children go
+    children: Entity,
+}
+
+/// An entity's row, holding its header and its children's rows.
+#[derive(Component, Debug)]
+pub struct HierarchyRow {
+    pub entity: Entity,
+}
+
+/// The part of a row with the entity's name in it.
+#[derive(Component, Debug)]
+pub struct HierarchyRowHeader {
+    pub entity: Entity,
+}
+
+/// The ▼ in front of an entity with children, collapses and expands them.
+#[derive(Component, Debug)]
+pub struct HierarchyToggle {
+    pub entity: Entity,
+}
+
+/// How close to the top or bottom of a row (as a fraction of its height) a row has to be dropped
+/// to go next to it instead of into it.
+const ROW_EDGE_ZONE: f32 = 0.25;
+
+/// The search field on top of the hierarchy, a [`TextInput`].
+#[derive(Component, Debug)]
+pub struct HierarchySearch;
+
+/// Which rows are collapsed and what's being searched for, kept here so it survives the dock
+/// getting rebuilt.
+#[derive(Resource, Default, Debug)]
+pub struct HierarchyState {
+    pub collapsed: HashSet<Entity>,
+    pub search: String,
+}
+
+pub fn spawn_hierarchy_panel(builder: &mut ChildBuilder, theme: &EditorTheme) {
+    builder
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(100.0),
+                row_gap: Val::Px(6.0),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|builder| {
+            // filled in from HierarchyState by update_hierarchy_search_field
+            spawn_text_input(
+                builder,
+                theme,
+                TextInput::default().with_placeholder("Search (name, t:Type)"),
+            )
+            .insert(HierarchySearch);
+            builder.spawn((
+                NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        width: Val::Percent(100.0),
+                        ..default()
+                    },
+                    ..default()
+                },
+                HierarchyList::default(),
+            ));
+        });
+}
+
+/// `Name` if it has one, otherwise something like "Entity 12v0 (PbrBundle)".
+pub fn entity_label(entity: &EntityRef) -> String {
+    if let Some(name) = entity.get::<Name>() {
+        return name.to_string();
+    }
+    let id = entity.id();
+    match entity_kind(entity) {
+        Some(kind) => format!("Entity {}v{} ({kind})", id.index(), id.generation()),
+        None => format!("Entity {}v{}", id.index(), id.generation()),
+    }
+}
+
+/// Guesses which bundle an entity was spawned with from its components.
+fn entity_kind(entity: &EntityRef) -> Option<&'static str> {
+    Some(if entity.contains::<Camera3d>() {
+        "Camera3dBundle"
+    } else if entity.contains::<Camera2d>() {
+        "Camera2dBundle"
+    } else if entity.contains::<PointLight>() {
+        "PointLightBundle"
+    } else if entity.contains::<DirectionalLight>() {
+        "DirectionalLightBundle"
+    } else if entity.contains::<SpotLight>() {
+        "SpotLightBundle"
+    } else if entity.contains::<Handle<Mesh>>() && entity.contains::<Handle<StandardMaterial>>() {
+        "PbrBundle"
+    } else if entity.contains::<Handle<Mesh>>() {
+        "MaterialMeshBundle"
+    } else if entity.contains::<Sprite>() {
+        "SpriteBundle"
+    } else if entity.contains::<Text>() {
+        "Text2dBundle"
+    } else if entity.contains::<Handle<Scene>>() {
+        "SceneBundle"
+    } else if entity.contains::<Visibility>() && entity.contains::<Transform>() {
+        "SpatialBundle"
+    } else if entity.contains::<Transform>() {
+        "TransformBundle"
+    } else {
+        return None;
+    })
+}
+
+/// Brings every [`HierarchyList`] up to date with the world. A list that was just spawned (like
+/// when the dock gets rebuilt) has no rows yet so it gets filled in from scratch, after that only
+/// the rows that changed are touched.
+pub fn update_hierarchy(
+    mut commands: Commands,
+    mut lists: Query<(Entity, &mut HierarchyList), With<Node>>,
+    scene: Query<EntityRef, SceneFilter>,
+    rows: Query<(), With<HierarchyRow>>,
+    ui_children: Query<&Children, With<Node>>,
+    reordered: Query<Entity, (Changed<Children>, SceneFilter)>,
+    reparented: Query<Entity, (Changed<Parent>, SceneFilter)>,
+    renamed: Query<Entity, (Changed<Name>, SceneFilter)>,
+    mut removed_children: RemovedComponents<Children>,
+    mut removed_parents: RemovedComponents<Parent>,
+    mut removed_names: RemovedComponents<Name>,
+    // the EntityRef query reads everything, so resources can only be read here too
+    state: Res<HierarchyState>,
+    show_internals: Res<ShowInternals>,
+    theme: Res<EditorTheme>,
+) {
+    let listed = |entity: Entity| {
+        scene
+            .get(entity)
+            .ok()
+            .filter(|entity| show_internals.shows(entity))
+    };
+    let removed_children: Vec<_> = removed_children.read().collect();
+    let removed_parents: Vec<_> = removed_parents.read().collect();
+    let removed_names: Vec<_> = removed_names.read().collect();
+
+    // the listed parent of a listed entity, `None` if it's at the root of the tree
+    let listed_parent = |entity: &EntityRef| {
+        entity
+            .get::<Parent>()
+            .map(Parent::get)
+            .filter(|parent| listed(*parent).is_some())
+    };
+    let depth = |entity: &EntityRef| {
+        let mut depth = 0;
+        let mut current = listed_parent(entity);
+        while let Some(parent) = current.and_then(listed) {
+            depth += 1;
+            current = listed_parent(&parent);
+        }
+        depth
+    };
+
+    for (list_entity, mut list) in &mut lists {
+        let list = list.bypass_change_detection();
+
+        // a whole different set of entities, start over
+        if show_internals.is_changed() && !show_internals.is_added() {
+            commands.entity(list_entity).despawn_descendants();
+            list.rows.clear();
+        }
+
+        // rows of entities that are gone. the rows of their children go with them, those get
+        // spawned again below if their entities are still around
+        let mut despawned = HashSet::new();
+        for (entity, nodes) in &list.rows {
+            if listed(*entity).is_none() && rows.contains(nodes.row) {
+                commands.entity(nodes.row).despawn_recursive();
+                despawned.insert(nodes.row);
+                despawned.extend(ui_children.iter_descendants(nodes.row));
+            }
+        }
+        list.rows.retain(|entity, nodes| {
+            listed(*entity).is_some() && rows.contains(nodes.row) && !despawned.contains(&nodes.row)
+        });
+
+        // `None` is the root of the tree
+        let mut dirty: HashSet<Option<Entity>> = HashSet::new();
+
+        // new rows, parents before their children
+        let mut new: Vec<_> = scene
+            .iter()
+            .filter(|entity| show_internals.shows(entity) && !list.rows.contains_key(&entity.id()))
+            .map(|entity| (depth(&entity), entity))
+            .collect();
+        new.sort_by_key(|(depth, entity)| (*depth, entity.id()));
+        for (_, entity) in new {
+            let nodes = spawn_row(
+                &mut commands,
+                &entity,
+                state.collapsed.contains(&entity.id()),
+                &theme,
+            );
+            list.rows.insert(entity.id(), nodes);
+            dirty.insert(listed_parent(&entity));
+        }
+
+        // children that moved around, or moved to another parent
+        for entity in reordered.iter().chain(removed_children.iter().copied()) {
+            if listed(entity).is_some() {
+                dirty.insert(Some(entity));
+            }
+        }
+        for entity in &reparented {
+            if let Some(entity) = listed(entity) {
+                dirty.insert(listed_parent(&entity));
+            }
+        }
+        if !removed_parents.is_empty() {
+            dirty.insert(None);
+        }
+
+        for parent in dirty {
+            let (container, children) = match parent {
+                Some(parent) => {
+                    let Some(nodes) = list.rows.get(&parent) else {
+                        continue;
+                    };
+                    let children: Vec<_> = scene
+                        .get(parent)
+                        .ok()
+                        .and_then(|parent| parent.get::<Children>())
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|child| list.rows.get(child).map(|nodes| nodes.row))
+                        .collect();
+                    let chevron = match (children.is_empty(), state.collapsed.contains(&parent)) {
+                        (true, _) => "",
+                        (false, true) => "▶",
+                        (false, false) => "▼",
+                    };
+                    commands
+                        .entity(nodes.chevron)
+                        .insert(row_text(chevron, &theme));
+                    (nodes.children, children)
+                }
+                None => {
+                    let mut roots: Vec<_> = scene
+                        .iter()
+                        .filter(|entity| {
+                            show_internals.shows(entity) && listed_parent(entity).is_none()
+                        })
+                        .map(|entity| entity.id())
+                        .collect();
+                    roots.sort();
+                    let roots = roots
+                        .iter()
+                        .filter_map(|root| list.rows.get(root).map(|nodes| nodes.row))
+                        .collect();
+                    (list_entity, roots)
+                }
+            };
+            commands.entity(container).replace_children(&children);
+        }
+
+        for entity in renamed.iter().chain(removed_names.iter().copied()) {
+            if let (Some(nodes), Some(entity)) = (list.rows.get(&entity), listed(entity)) {
+                commands
+                    .entity(nodes.label)
+                    .insert(row_text(&entity_label(&entity), &theme));
+            }
+        }
+    }
+}
+
+fn row_style(theme: &EditorTheme) -> TextStyle {
+    TextStyle {
+        font: theme.font.clone(),
+        font_size: 14.3,
+        color: Color::WHITE,
+    }
+}
+
+fn row_text(text: &str, theme: &EditorTheme) -> Text {
+    Text::from_section(text, row_style(theme))
+}
+
+/// Greyed out, for rows that are only there for their children.
+fn dimmed_text(text: &str, theme: &EditorTheme) -> Text {
+    Text::from_section(
+        text,
+        TextStyle {
+            color: Color::WHITE.with_a(0.5),
+            ..row_style(theme)
+        },
+    )
+}
+
+/// `label` with the parts in `highlights` in the accent color.
+fn highlighted_text(label: &str, highlights: &[Range<usize>], theme: &EditorTheme) -> Text {
+    let style = row_style(theme);
+    let highlight = TextStyle {
+        color: theme.accent,
+        ..style.clone()
+    };
+    let mut sections = Vec::new();
+    let mut end = 0;
+    for range in highlights {
+        if range.start > end {
+            sections.push(TextSection::new(&label[end..range.start], style.clone()));
+        }
+        sections.push(TextSection::new(&label[range.clone()], highlight.clone()));
+        end = range.end;
+    }
+    if end < label.len() || sections.is_empty() {
+        sections.push(TextSection::new(&label[end..], style));
+    }
+    Text::from_sections(sections)
+}
+
+/// Spawns an entity's row without a parent, it gets put in place once every new row exists.
+fn spawn_row(
+    commands: &mut Commands,
+    entity: &EntityRef,
+    collapsed: bool,
+    theme: &EditorTheme,
+) -> HierarchyRowNodes {
+    let id = entity.id();
+    let chevron = commands
+        .spawn((
+            TextBundle {
+                text: row_text("", theme),
+                style: Style {
+                    width: Val::Px(12.0),
+                    ..default()
+                },
+                ..default()
+            },
+            HierarchyToggle { entity: id },
+        ))
+        .id();
+    let label = commands
+        .spawn((
+            TextBundle {
+                text: row_text(&entity_label(entity), theme),
+                ..default()
+            },
+            Pickable::IGNORE,
+        ))
+        .id();
+    let header = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(4.8),
+                    padding: UiRect::vertical(Val::Px(2.4)),
+                    ..default()
+                },
+                ..default()
+            },
+            HierarchyRowHeader { entity: id },
+        ))
+        .push_children(&[chevron, label])
+        .id();
+    let children = commands
+        .spawn(NodeBundle {
+            style: Style {
+                display: if collapsed {
+                    Display::None
+                } else {
+                    Display::Flex
+                },
+                flex_direction: FlexDirection::Column,
+                margin: UiRect::left(Val::Px(14.4)),
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+    let row = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+            HierarchyRow { entity: id },
+        ))
+        .push_children(&[header, children])
+        .id();
+    HierarchyRowNodes {
+        row,
+        chevron,
+        label,
+        children,
+    }
+}
+
+/// Searches for what's typed into the search field. Escape clears it.
+pub fn edit_hierarchy_search(
+    mut changed: EventReader<TextInputChanged>,
+    mut cancelled: EventReader<TextInputCancelled>,
+    mut fields: Query<&mut TextInput, With<HierarchySearch>>,
+    mut state: ResMut<HierarchyState>,
+) {
+    for changed in changed.read() {
+        if fields.contains(changed.entity) {
+            state.search.clone_from(&changed.text);
+        }
+    }
+    for cancelled in cancelled.read() {
+        if let Ok(mut field) = fields.get_mut(cancelled.entity) {
+            field.set_text("");
+            state.search.clear();
+        }
+    }
+}
+
+/// Puts the search back into the search field when the dock gets rebuilt.
+pub fn update_hierarchy_search_field(
+    state: Res<HierarchyState>,
+    mut fields: Query<&mut TextInput, Added<HierarchySearch>>,
+) {
+    for mut field in &mut fields {
+        field.set_text(state.search.clone());
+    }
+}
+
+/// Hides the rows that don't match the search and highlights what matched in the ones that do.
+/// Their ancestors stay visible, greyed out if they don't match themselves, and get expanded.
+pub fn filter_hierarchy(
+    mut lists: Query<&mut HierarchyList, With<Node>>,
+    scene: Query<EntityRef, SceneFilter>,
+    components: &Components,
+    added: Query<(), Added<HierarchyRow>>,
+    renamed: Query<(), (Changed<Name>, SceneFilter)>,
+    reparented: Query<(), (Changed<Parent>, SceneFilter)>,
+    mut removed_parents: RemovedComponents<Parent>,
+    mut styles: Query<&mut Style, With<Node>>,
+    mut texts: Query<&mut Text, With<Node>>,
+    state: Res<HierarchyState>,
+    show_internals: Res<ShowInternals>,
+    theme: Res<EditorTheme>,
+    mut last_search: Local<String>,
+) {
+    let unparented = removed_parents.read().count() > 0;
+    let query = SearchQuery::parse(&state.search);
+    let search_changed = *last_search != state.search;
+    let was_all = SearchQuery::parse(&last_search).is_all();
+    if search_changed {
+        last_search.clone_from(&state.search);
+    }
+    // rows start out unfiltered, so there's nothing to do until something gets searched for
+    if query.is_all() && was_all {
+        return;
+    }
+    let scene_changed = !added.is_empty() || !renamed.is_empty() || !reparented.is_empty();
+    if !search_changed && !scene_changed && !unparented {
+        return;
+    }
+
+    let mut matched = HashSet::new();
+    let mut visible = HashSet::new();
+    let mut expanded = HashSet::new();
+    if !query.is_all() {
+        for entity in scene.iter().filter(|entity| show_internals.shows(entity)) {
+            if !query.matches(&entity_label(&entity), &entity, components) {
+                continue;
+            }
+            matched.insert(entity.id());
+            visible.insert(entity.id());
+            let mut parent = entity.get::<Parent>().map(Parent::get);
+            while let Some(ancestor) = parent
+                .and_then(|parent| scene.get(parent).ok())
+                .filter(|ancestor| show_internals.shows(ancestor))
+            {
+                expanded.insert(ancestor.id());
+                // the rest of the way up has been done already
+                if !visible.insert(ancestor.id()) {
+                    break;
+                }
+                parent = ancestor.get::<Parent>().map(Parent::get);
+            }
+        }
+    }
+
+    for mut list in &mut lists {
+        let list = &mut *list;
+        for (entity, nodes) in &list.rows {
+            let Ok(entity_ref) = scene.get(*entity) else {
+                continue;
+            };
+            let shown = query.is_all() || visible.contains(entity);
+            if let Ok(mut style) = styles.get_mut(nodes.row) {
+                style.display = if shown { Display::Flex } else { Display::None };
+            }
+            if !shown {
+                continue;
+            }
+
+            let label = entity_label(&entity_ref);
+            if let Ok(mut text) = texts.get_mut(nodes.label) {
+                *text = if query.is_all() {
+                    row_text(&label, &theme)
+                } else if matched.contains(entity) {
+                    highlighted_text(&label, &query.highlights(&label), &theme)
+                } else {
+                    dimmed_text(&label, &theme)
+                };
+            }
+
+            if expanded.contains(entity) && state.collapsed.contains(entity) {
+                if let Ok(mut style) = styles.get_mut(nodes.children) {
+                    style.display = Display::Flex;
+                }
+                if let Ok(mut chevron) = texts.get_mut(nodes.chevron) {
+                    chevron.sections[0].value = "▼".to_string();
+                }
+                list.revealed.push(*entity);
+            }
+        }
+    }
+}
+
+/// Forgets that the rows [`filter_hierarchy`] expanded were collapsed, so they can be collapsed
+/// again by hand. The filter can't do it itself since it looks at the whole world.
+pub fn reveal_hierarchy_matches(
+    mut lists: Query<&mut HierarchyList>,
+    mut state: ResMut<HierarchyState>,
+) {
+    for mut list in &mut lists {
+        for entity in std::mem::take(&mut list.revealed) {
+            state.collapsed.remove(&entity);
+        }
+    }
+}
+
+/// Collapses and expands rows when their ▼ is clicked.
+pub fn toggle_hierarchy_rows(
+    mut clicks: EventReader<Pointer<Click>>,
+    toggles: Query<&HierarchyToggle>,
+    lists: Query<&HierarchyList>,
+    mut styles: Query<&mut Style>,
+    mut texts: Query<&mut Text>,
+    mut state: ResMut<HierarchyState>,
+) {
+    for click in clicks.read() {
+        if click.button != PointerButton::Primary {
+            continue;
+        }
+        let Ok(toggle) = toggles.get(click.target) else {
+            continue;
+        };
+        // rows without children have nothing to collapse
+        let Ok(mut chevron) = texts.get_mut(click.target) else {
+            continue;
+        };
+        if chevron.sections[0].value.is_empty() {
+            continue;
+        }
+        let collapsed = !state.collapsed.remove(&toggle.entity);
+        if collapsed {
+            state.collapsed.insert(toggle.entity);
+        }
+        chevron.sections[0].value = if collapsed { "▶" } else { "▼" }.to_string();
+        for list in &lists {
+            let Some(nodes) = list.rows.get(&toggle.entity) else {
+                continue;
+            };
+            if let Ok(mut style) = styles.get_mut(nodes.children) {
+                style.display = if collapsed {
+                    Display::None
+                } else {
+                    Display::Flex
+                };
+            }
+        }
+    }
+}
+
+/// Selects a row's entity when it's clicked anywhere but its ▼, and frames it in the viewport when
+/// it's double-clicked.
+pub fn select_hierarchy_rows(
+    mut clicks: EventReader<Pointer<Click>>,
+    headers: Query<(&HierarchyRowHeader, &Node, &GlobalTransform)>,
+    toggles: Query<(), With<HierarchyToggle>>,
+    parents: Query<&Parent>,
+    mut selection: ResMut<Selection>,
+    mut actions: EventWriter<MenuAction>,
+    time: Res<Time>,
+    mut last_click: Local<Option<(Entity, f32)>>,
+) {
+    for click in clicks.read() {
+        if click.button != PointerButton::Primary || toggles.contains(click.target) {
+            continue;
+        }
+        let Some(header) = find_header(click.target, &headers, &parents) else {
+            continue;
+        };
+        let (header, _, _) = headers.get(header).unwrap();
+        if selection.entity != Some(header.entity) {
+            selection.entity = Some(header.entity);
+        }
+        let now = time.elapsed_seconds();
+        match *last_click {
+            Some((entity, at)) if entity == header.entity && now - at < DOUBLE_CLICK_TIME => {
+                actions.send(MenuAction::FrameSelected);
+                *last_click = None;
+            }
+            _ => *last_click = Some((header.entity, now)),
+        }
+    }
+}
+
+pub fn highlight_selected_rows(
+    selection: Res<Selection>,
+    mut headers: Query<(&HierarchyRowHeader, &mut BackgroundColor)>,
+    added: Query<(), Added<HierarchyRowHeader>>,
+    theme: Res<EditorTheme>,
+) {
+    if !selection.is_changed() && added.is_empty() {
+        return;
+    }
+    for (header, mut background) in &mut headers {
+        background.0 = if selection.entity == Some(header.entity) {
+            theme.accent.with_a(0.35)
+        } else {
+            Color::NONE
+        };
+    }
+}
+
+/// Where a dragged row would go.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HierarchyDrop {
+    /// last child of this entity
+    Into(Entity),
+    /// right before this entity, under the same parent
+    Before(Entity),
+    /// right after this entity, under the same parent
+    After(Entity),
+    /// no parent at all. roots are listed in the order they were spawned, so there's no
+    /// reordering them
+    Root,
+}
+
+/// The row being dragged right now, where it would go if it was let go of, and whether it's allowed
+/// to go there.
+#[derive(Resource, Default, Debug)]
+pub struct HierarchyDrag {
+    pub entity: Option<Entity>,
+    pub target: Option<(HierarchyDrop, bool)>,
+}
+
+/// Walks up from whatever was picked to the row header it's in.
+fn find_header(
+    mut entity: Entity,
+    headers: &Query<(&HierarchyRowHeader, &Node, &GlobalTransform)>,
+    parents: &Query<&Parent>,
+) -> Option<Entity> {
+    loop {
+        if headers.contains(entity) {
+            return Some(entity);
+        }
+        entity = parents.get(entity).ok()?.get();
+    }
+}
+
+/// Whether `entity` is `ancestor` or somewhere under it.
+fn is_descendant_of(mut entity: Entity, ancestor: Entity, parents: &Query<&Parent>) -> bool {
+    loop {
+        if entity == ancestor {
+            return true;
+        }
+        match parents.get(entity) {
+            Ok(parent) => entity = parent.get(),
+            Err(_) => return false,
+        }
+    }
+}
+
+pub fn start_hierarchy_drag(
+    mut drag_starts: EventReader<Pointer<DragStart>>,
+    headers: Query<(&HierarchyRowHeader, &Node, &GlobalTransform)>,
+    parents: Query<&Parent>,
+    mut hierarchy_drag: ResMut<HierarchyDrag>,
+) {
+    for drag_start in drag_starts.read() {
+        if drag_start.button != PointerButton::Primary {
+            continue;
+        }
+        let Some(header) = find_header(drag_start.target, &headers, &parents) else {
+            continue;
+        };
+        let (header, _, _) = headers.get(header).unwrap();
+        *hierarchy_drag = HierarchyDrag {
+            entity: Some(header.entity),
+            target: None,
+        };
+    }
+}
+
+/// Works out where the dragged row would go from whatever is under the pointer, and shows it with
+/// the drop preview, in red if it can't go there.
+pub fn update_hierarchy_drag(
+    mut drags: EventReader<Pointer<Drag>>,
+    mut drag_overs: EventReader<Pointer<DragOver>>,
+    mut hierarchy_drag: ResMut<HierarchyDrag>,
+    headers: Query<(&HierarchyRowHeader, &Node, &GlobalTransform)>,
+    lists: Query<(&Node, &GlobalTransform), With<HierarchyList>>,
+    bodies: Query<(&DockPanelBody, &Node, &GlobalTransform)>,
+    parents: Query<&Parent>,
+    pointer_windows: PointerWindows,
+    mut preview: Query<(&mut Style, &mut BackgroundColor), With<DropPreview>>,
+    ui_scale: Res<UiScale>,
+    theme: Res<EditorTheme>,
+) {
+    let Some(dragged) = hierarchy_drag.entity else {
+        drags.clear();
+        drag_overs.clear();
+        return;
+    };
+
+    // drag events keep coming while the pointer is over nothing or over the dragged row itself,
+    // drag overs don't
+    let moved = drags.read().count() > 0;
+    let mut hovered = None;
+    for drag_over in drag_overs.read() {
+        let position = pointer_windows.position(&drag_over.pointer_location);
+        let mut entity = drag_over.target;
+        let found = loop {
+            if let Ok((header, node, transform)) = headers.get(entity) {
+                if header.entity == dragged {
+                    break None;
+                }
+                let rect = node.logical_rect(transform);
+                let relative = (position.y - rect.min.y) / rect.height().max(1.0);
+                // a thin line between rows, or the whole row when dropping into it
+                let (drop, rect) = if relative < ROW_EDGE_ZONE {
+                    let line =
+                        Rect::new(rect.min.x, rect.min.y - 1.5, rect.max.x, rect.min.y + 1.5);
+                    (HierarchyDrop::Before(header.entity), line)
+                } else if relative > 1.0 - ROW_EDGE_ZONE {
+                    let line =
+                        Rect::new(rect.min.x, rect.max.y - 1.5, rect.max.x, rect.max.y + 1.5);
+                    (HierarchyDrop::After(header.entity), line)
+                } else {
+                    (HierarchyDrop::Into(header.entity), rect)
+                };
+                break Some((drop, rect));
+            }
+            // the empty space under the rows
+            if let Ok((node, transform)) = lists.get(entity) {
+                break Some((HierarchyDrop::Root, node.logical_rect(transform)));
+            }
+            if let Ok((body, node, transform)) = bodies.get(entity) {
+                if body.panel == PanelId::HIERARCHY {
+                    break Some((HierarchyDrop::Root, node.logical_rect(transform)));
+                }
+            }
+            match parents.get(entity) {
+                Ok(parent) => entity = parent.get(),
+                Err(_) => break None,
+            }
+        };
+        hovered = hovered.or(found);
+    }
+    if !moved && hovered.is_none() {
+        return;
+    }
+
+    let Ok((mut style, mut background)) = preview.get_single_mut() else {
+        return;
+    };
+    match hovered {
+        Some((drop, rect)) => {
+            // an entity can't go under itself or anything under it
+            let new_parent = match drop {
+                HierarchyDrop::Into(entity) => Some(entity),
+                HierarchyDrop::Before(entity) | HierarchyDrop::After(entity) => {
+                    parents.get(entity).ok().map(Parent::get)
+                }
+                HierarchyDrop::Root => None,
+            };
+            let allowed =
+                new_parent.is_none_or(|parent| !is_descendant_of(parent, dragged, &parents));
+
+            let scale = ui_scale.0 as f32;
+            background.0 = if allowed { theme.accent } else { theme.error }.with_a(0.35);
+            style.display = Display::Flex;
+            style.left = Val::Px(rect.min.x / scale);
+            style.top = Val::Px(rect.min.y / scale);
+            style.width = Val::Px(rect.width() / scale);
+            style.height = Val::Px(rect.height() / scale);
+            hierarchy_drag.target = Some((drop, allowed));
+        }
+        None => {
+            style.display = Display::None;
+            hierarchy_drag.target = None;
+        }
+    }
+}
+
+/// Reparents the dragged entity when it's dropped somewhere it's allowed to go, keeping it where it
+/// is in the world.
+pub fn end_hierarchy_drag(
+    mut commands: Commands,
+    mut drag_ends: EventReader<Pointer<DragEnd>>,
+    mut hierarchy_drag: ResMut<HierarchyDrag>,
+    parents: Query<&Parent>,
+    children: Query<&Children>,
+    transforms: Query<&GlobalTransform>,
+    mut preview: Query<&mut Style, With<DropPreview>>,
+) {
+    for drag_end in drag_ends.read() {
+        if drag_end.button != PointerButton::Primary || hierarchy_drag.entity.is_none() {
+            continue;
+        }
+        let HierarchyDrag { entity, target } = std::mem::take(&mut *hierarchy_drag);
+        if let Ok(mut style) = preview.get_single_mut() {
+            style.display = Display::None;
+        }
+        let (Some(entity), Some((drop, true))) = (entity, target) else {
+            continue;
+        };
+
+        let (parent, index) = match drop {
+            HierarchyDrop::Into(parent) => (Some(parent), None),
+            HierarchyDrop::Before(sibling) | HierarchyDrop::After(sibling) => {
+                match parents.get(sibling).map(Parent::get) {
+                    Ok(parent) => {
+                        // where the sibling is once the dragged entity is taken out
+                        let index = children
+                            .get(parent)
+                            .into_iter()
+                            .flatten()
+                            .filter(|child| **child != entity)
+                            .position(|child| *child == sibling)
+                            .unwrap_or(0);
+                        let after = matches!(drop, HierarchyDrop::After(_));
+                        (Some(parent), Some(index + after as usize))
+                    }
+                    Err(_) => (None, None),
+                }
+            }
+            HierarchyDrop::Root => (None, None),
+        };
+
+        if let Ok(global) = transforms.get(entity) {
+            let transform = match parent.and_then(|parent| transforms.get(parent).ok()) {
+                Some(parent) => global.reparented_to(parent),
+                None => global.compute_transform(),
+            };
+            commands.entity(entity).insert(transform);
+        }
+        match (parent, index) {
+            (Some(parent), Some(index)) => {
+                commands.entity(parent).insert_children(index, &[entity]);
+            }
+            (Some(parent), None) => {
+                commands.entity(parent).add_child(entity);
+            }
+            (None, _) => {
+                commands.entity(entity).remove_parent();
+            }
+        }
+    }
+}
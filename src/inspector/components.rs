@@ -0,0 +1,406 @@
+//! adding and removing components from the inspector. the "Add Component" button under an entity's
+//! components opens a searchable list of every registered component that has `ReflectDefault`
+//! (so there's something to insert), and right-clicking a component's header opens a menu to
+//! remove it, reset it to its default, or copy its value and paste it into another entity's.
+use std::any::TypeId;
+
+use bevy::prelude::*;
+use bevy::reflect::TypeRegistry;
+use bevy_mod_picking::prelude::*;
+
+use crate::floating::PointerWindows;
+use crate::theme::EditorTheme;
+use crate::widgets::popup::{find_popup, popup_position, spawn_popup, Popup, PopupEntry};
+use crate::widgets::text_input::{spawn_text_input, TextInput, TextInputChanged, TextInputFocus};
+use crate::widgets::CollapsibleHeader;
+
+/// How many matches the add component list shows, the search narrows it down from there.
+const MAX_RESULTS: usize = 16;
+
+/// The collapsible showing `component` of `entity`.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct InspectorComponent {
+    pub entity: Entity,
+    pub component: TypeId,
+}
+
+/// The button that opens the list of components to add to `entity`.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct AddComponentButton {
+    pub entity: Entity,
+}
+
+/// The search field of an add component popup.
+#[derive(Component, Debug)]
+pub struct AddComponentSearch;
+
+/// The node the matches of an add component popup's search are spawned into.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct AddComponentList {
+    pub entity: Entity,
+    pub search: Entity,
+}
+
+/// An entry in one of the popups, clicking it sends its [`ComponentEdit`].
+#[derive(Component, Clone, Debug)]
+pub struct ComponentMenuEntry(pub ComponentEdit);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComponentChange {
+    /// inserts the default value
+    Add,
+    Remove,
+    /// sets it back to the default value
+    Reset,
+    /// copies the value to the [`ComponentClipboard`]
+    Copy,
+    /// sets it to what's in the [`ComponentClipboard`], if that's the same type
+    Paste,
+}
+
+/// Sent to add, remove, reset, copy or paste a whole component, see [`apply_component_edits`].
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ComponentEdit {
+    pub entity: Entity,
+    pub component: TypeId,
+    pub change: ComponentChange,
+}
+
+/// The last component value copied in the inspector.
+#[derive(Resource, Default)]
+pub struct ComponentClipboard {
+    pub value: Option<(TypeId, Box<dyn Reflect>)>,
+}
+
+impl ComponentClipboard {
+    /// Whether what's copied can be pasted into a `component`. A pasted [`Parent`] or [`Children`]
+    /// wouldn't match the other side of the hierarchy, so those can't be.
+    pub fn holds(&self, component: TypeId) -> bool {
+        let hierarchy = [TypeId::of::<Parent>(), TypeId::of::<Children>()];
+        matches!(self.value, Some((type_id, _)) if type_id == component)
+            && !hierarchy.contains(&component)
+    }
+}
+
+fn text_style(theme: &EditorTheme, color: Color) -> TextStyle {
+    TextStyle {
+        font: theme.font.clone(),
+        font_size: 14.3,
+        color,
+    }
+}
+
+pub fn spawn_add_component_button(builder: &mut ChildBuilder, theme: &EditorTheme, entity: Entity) {
+    builder
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::Flex,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    width: Val::Percent(100.0),
+                    height: Val::Px(22.0),
+                    margin: UiRect::top(Val::Px(6.0)),
+                    ..default()
+                },
+                background_color: BackgroundColor(theme.input_background),
+                ..default()
+            },
+            AddComponentButton { entity },
+        ))
+        .with_children(|builder| {
+            builder.spawn((
+                TextBundle::from_section("Add Component", text_style(theme, Color::WHITE)),
+                Pickable::IGNORE,
+            ));
+        });
+}
+
+/// Spawns an entry for a popup, or a dimmed one that can't be clicked if `edit` is `None`.
+fn spawn_entry(
+    builder: &mut ChildBuilder,
+    theme: &EditorTheme,
+    label: &str,
+    edit: Option<ComponentEdit>,
+) {
+    let color = if edit.is_some() {
+        Color::WHITE
+    } else {
+        Color::WHITE.with_a(0.4)
+    };
+    let mut commands = builder.spawn(NodeBundle {
+        style: Style {
+            padding: UiRect::axes(Val::Px(4.8), Val::Px(1.2)),
+            ..default()
+        },
+        ..default()
+    });
+    if let Some(edit) = edit {
+        commands.insert((ComponentMenuEntry(edit), PopupEntry));
+    }
+    commands.with_children(|builder| {
+        builder.spawn((
+            TextBundle::from_section(label, text_style(theme, color)),
+            Pickable::IGNORE,
+        ));
+    });
+}
+
+/// The components that could be added to `entity`, by name, that match every word of `search`.
+fn addable_components(
+    world: &World,
+    registry: &TypeRegistry,
+    entity: Entity,
+    search: &str,
+) -> Vec<(String, TypeId)> {
+    let Some(entity) = world.get_entity(entity) else {
+        return Vec::new();
+    };
+    let search = search.to_lowercase();
+    let mut components: Vec<_> = registry
+        .iter()
+        .filter(|registration| {
+            registration.data::<ReflectComponent>().is_some()
+                && registration.data::<ReflectDefault>().is_some()
+                && !entity.contains_type_id(registration.type_id())
+        })
+        .map(|registration| {
+            let path = registration.type_info().type_path_table();
+            (path.short_path().to_string(), registration.type_id())
+        })
+        .filter(|(name, _)| {
+            let name = name.to_lowercase();
+            search.split_whitespace().all(|word| name.contains(word))
+        })
+        .collect();
+    components.sort();
+    components
+}
+
+/// Opens the add component popup when its button is clicked, and the menu of a component when its
+/// header is right-clicked.
+pub fn open_component_menus(
+    mut commands: Commands,
+    mut clicks: EventReader<Pointer<Click>>,
+    buttons: Query<(&AddComponentButton, &Node, &GlobalTransform)>,
+    headers: Query<&Parent, With<CollapsibleHeader>>,
+    components: Query<&InspectorComponent>,
+    popups: Query<(Entity, &Popup)>,
+    mut focus: ResMut<TextInputFocus>,
+    clipboard: Res<ComponentClipboard>,
+    registry: Res<AppTypeRegistry>,
+    theme: Res<EditorTheme>,
+    ui_scale: Res<UiScale>,
+    pointer_windows: PointerWindows,
+) {
+    for click in clicks.read() {
+        // clicking the button or header again closes it
+        let open = popups.iter().find(|(_, popup)| popup.owner == click.target);
+        if let (Some((popup, _)), true) = (
+            open,
+            buttons.contains(click.target) || headers.contains(click.target),
+        ) {
+            commands.entity(popup).despawn_recursive();
+            continue;
+        }
+        if let (Ok((button, node, transform)), PointerButton::Primary) =
+            (buttons.get(click.target), click.button)
+        {
+            let position = popup_position(node, transform, &ui_scale);
+            spawn_popup(&mut commands, &theme, click.target, position).with_children(|builder| {
+                let search = spawn_text_input(
+                    builder,
+                    &theme,
+                    TextInput::new("").with_placeholder("Search components"),
+                )
+                .insert(AddComponentSearch)
+                .id();
+                focus.0 = Some(search);
+                // filled in by update_add_component_lists
+                builder.spawn((
+                    NodeBundle {
+                        style: Style {
+                            display: Display::Flex,
+                            flex_direction: FlexDirection::Column,
+                            width: Val::Px(220.0),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    AddComponentList {
+                        entity: button.entity,
+                        search,
+                    },
+                ));
+            });
+            continue;
+        }
+        if click.button != PointerButton::Secondary {
+            continue;
+        }
+        let Ok(component) = headers
+            .get(click.target)
+            .and_then(|parent| components.get(parent.get()))
+        else {
+            continue;
+        };
+        let registry = registry.read();
+        let has_default = registry
+            .get_type_data::<ReflectDefault>(component.component)
+            .is_some();
+        let edit = |change| ComponentEdit {
+            entity: component.entity,
+            component: component.component,
+            change,
+        };
+        let position = pointer_windows.position(&click.pointer_location) / ui_scale.0 as f32;
+        spawn_popup(&mut commands, &theme, click.target, position).with_children(|builder| {
+            spawn_entry(
+                builder,
+                &theme,
+                "Remove",
+                Some(edit(ComponentChange::Remove)),
+            );
+            spawn_entry(
+                builder,
+                &theme,
+                "Reset to Default",
+                has_default.then(|| edit(ComponentChange::Reset)),
+            );
+            spawn_entry(builder, &theme, "Copy", Some(edit(ComponentChange::Copy)));
+            spawn_entry(
+                builder,
+                &theme,
+                "Paste",
+                clipboard
+                    .holds(component.component)
+                    .then(|| edit(ComponentChange::Paste)),
+            );
+        });
+    }
+}
+
+/// Fills in the matches of an add component popup when it opens and when its search changes.
+pub fn update_add_component_lists(
+    world: &World,
+    mut commands: Commands,
+    mut changes: EventReader<TextInputChanged>,
+    lists: Query<(Entity, &AddComponentList)>,
+    added: Query<(), Added<AddComponentList>>,
+    searches: Query<&TextInput, With<AddComponentSearch>>,
+    theme: Res<EditorTheme>,
+) {
+    let changed: Vec<_> = changes.read().map(|change| change.entity).collect();
+    for (entity, list) in &lists {
+        if !added.contains(entity) && !changed.contains(&list.search) {
+            continue;
+        }
+        let search = searches.get(list.search).map_or("", |input| &input.text);
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let components = addable_components(world, &registry, list.entity, search);
+        commands
+            .entity(entity)
+            .despawn_descendants()
+            .with_children(|builder| {
+                for (name, component) in components.iter().take(MAX_RESULTS) {
+                    let edit = ComponentEdit {
+                        entity: list.entity,
+                        component: *component,
+                        change: ComponentChange::Add,
+                    };
+                    spawn_entry(builder, &theme, name, Some(edit));
+                }
+                let label = match components.len() {
+                    0 => "No matches".to_string(),
+                    len if len > MAX_RESULTS => format!("{} more...", len - MAX_RESULTS),
+                    _ => return,
+                };
+                spawn_entry(builder, &theme, &label, None);
+            });
+    }
+}
+
+/// Sends the [`ComponentEdit`] of the entry that's clicked and closes its popup.
+pub fn click_component_menus(
+    mut commands: Commands,
+    mut clicks: EventReader<Pointer<Click>>,
+    entries: Query<&ComponentMenuEntry>,
+    popups: Query<(Entity, &Popup)>,
+    parents: Query<&Parent>,
+    mut edits: EventWriter<ComponentEdit>,
+) {
+    for click in clicks.read() {
+        if click.button != PointerButton::Primary {
+            continue;
+        }
+        let Ok(ComponentMenuEntry(edit)) = entries.get(click.target) else {
+            continue;
+        };
+        edits.send(*edit);
+        if let Some(popup) = find_popup(click.target, &popups, &parents) {
+            commands.entity(popup).despawn_recursive();
+        }
+    }
+}
+
+/// Applies [`ComponentEdit`]s to the entities they're for.
+pub fn apply_component_edits(world: &mut World) {
+    let edits: Vec<_> = world
+        .resource_mut::<Events<ComponentEdit>>()
+        .drain()
+        .collect();
+    if edits.is_empty() {
+        return;
+    }
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    world.resource_scope(|world, mut clipboard: Mut<ComponentClipboard>| {
+        for ComponentEdit {
+            entity,
+            component,
+            change,
+        } in edits
+        {
+            let Some(reflect_component) = registry.get_type_data::<ReflectComponent>(component)
+            else {
+                continue;
+            };
+            let Some(mut entity) = world.get_entity_mut(entity) else {
+                continue;
+            };
+            let default = || {
+                registry
+                    .get_type_data::<ReflectDefault>(component)
+                    .map(ReflectDefault::default)
+            };
+            match change {
+                ComponentChange::Add | ComponentChange::Reset => {
+                    if let Some(default) = default() {
+                        reflect_component.insert(&mut entity, &*default);
+                    }
+                }
+                // the other side of the hierarchy has to change along with these
+                ComponentChange::Remove if component == TypeId::of::<Parent>() => {
+                    entity.remove_parent();
+                }
+                ComponentChange::Remove if component == TypeId::of::<Children>() => {
+                    entity.clear_children();
+                }
+                ComponentChange::Remove => reflect_component.remove(&mut entity),
+                ComponentChange::Copy => {
+                    if let Some(value) = reflect_component.reflect((&entity).into()) {
+                        clipboard.value = Some((component, value.clone_value()));
+                    }
+                }
+                ComponentChange::Paste => {
+                    let Some((_, value)) = &clipboard.value else {
+                        continue;
+                    };
+                    if clipboard.holds(component) && entity.contains_type_id(component) {
+                        reflect_component.apply(&mut entity, &**value);
+                    }
+                }
+            }
+        }
+    });
+}
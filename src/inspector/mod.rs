@@ -0,0 +1,837 @@
+//! the inspector panel. it shows every reflected component of the [`Selection`] in a collapsible,
+//! with a widget for each value inside it: numbers are [`NumericField`]s (vectors get one per
+//! axis), bools are checkboxes, enums are dropdowns, `Quat`s show up as euler angles in degrees and
+//! `Color`s as a color picker and rgba sliders.
+//! fields keep up with the world every frame, and the whole thing gets rebuilt when the entity's
+//! components (or the variants of the enums in them) change.
+//! adding and removing whole components is in [`components`].
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use bevy::prelude::*;
+use bevy::reflect::{
+    DynamicEnum, DynamicVariant, GetPath, ReflectRef, TypeInfo, TypeRegistry, VariantInfo,
+};
+use bevy::utils::get_short_name;
+
+use self::components::{spawn_add_component_button, InspectorComponent};
+use crate::selection::Selection;
+use crate::theme::EditorTheme;
+use crate::widgets::checkbox::{spawn_checkbox, Checkbox, CheckboxChanged};
+use crate::widgets::color::{spawn_color_field, ColorChanged, ColorField};
+use crate::widgets::dropdown::{spawn_dropdown, Dropdown, DropdownChanged};
+use crate::widgets::numeric::{spawn_numeric_field, Number, NumericField, ValueChanged};
+use crate::widgets::text_input::TextInput;
+use crate::widgets::vector::spawn_vector_field;
+use crate::widgets::{spawn_nested_collapsible, Collapsible};
+
+pub mod components;
+
+/// The node the inspector's content is spawned into.
+#[derive(Component, Default)]
+pub struct InspectorRoot {
+    built: bool,
+    /// the entity shown and a hash of the shape of its components
+    shown: Option<(Entity, u64)>,
+}
+
+/// What an [`InspectorField`] shows and how it's edited.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldKind {
+    /// `f32` or `f64`
+    Float,
+    /// any integer
+    Integer,
+    /// a checkbox
+    Bool,
+    /// a dropdown with the variants that don't have fields, there's nothing to fill those in with
+    Enum,
+    /// one of a `Quat`'s XYZ euler angles, in degrees
+    Euler(usize),
+    /// one of a `Color`'s rgba channels
+    ColorChannel(usize),
+    /// a `Color` as a swatch opening a color picker
+    Color,
+    /// shown but not editable yet, like strings and handles
+    ReadOnly,
+}
+
+/// A value in the inspector, found at `path` in the `component` of `entity`.
+#[derive(Component, Clone, Debug)]
+pub struct InspectorField {
+    pub entity: Entity,
+    pub component: TypeId,
+    pub path: String,
+    pub kind: FieldKind,
+}
+
+impl InspectorField {
+    fn join(&self, access: &str, kind: FieldKind) -> Self {
+        Self {
+            path: format!("{}{access}", self.path),
+            kind,
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldChange {
+    /// for numbers, in degrees for euler angles
+    Set(Number),
+    SetBool(bool),
+    /// switches an enum to the variant with this name
+    SetVariant(String),
+    SetColor(Color),
+}
+
+/// Sent to change the value behind an [`InspectorField`], see [`apply_inspector_edits`].
+#[derive(Event, Clone, Debug)]
+pub struct InspectorEdit {
+    pub field: InspectorField,
+    pub change: FieldChange,
+}
+
+pub fn spawn_inspector_panel(builder: &mut ChildBuilder, _theme: &EditorTheme) {
+    // the inspector has 12px of padding instead of 6
+    builder.spawn((
+        NodeBundle {
+            style: Style {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                width: Val::Percent(100.0),
+                padding: UiRect::all(Val::Px(6.0)),
+                row_gap: Val::Px(2.4),
+                ..default()
+            },
+            ..default()
+        },
+        InspectorRoot::default(),
+    ));
+}
+
+fn field_style(theme: &EditorTheme) -> TextStyle {
+    TextStyle {
+        font: theme.font.clone(),
+        font_size: 14.3,
+        color: Color::WHITE,
+    }
+}
+
+fn read_field<'w>(
+    world: &'w World,
+    registry: &TypeRegistry,
+    field: &InspectorField,
+) -> Option<&'w dyn Reflect> {
+    let reflect_component = registry.get_type_data::<ReflectComponent>(field.component)?;
+    let component = reflect_component.reflect(world.get_entity(field.entity)?)?;
+    component.reflect_path(field.path.as_str()).ok()
+}
+
+fn is_float(value: &dyn Reflect) -> bool {
+    value.is::<f32>() || value.is::<f64>()
+}
+
+fn is_integer(value: &dyn Reflect) -> bool {
+    macro_rules! any_of {
+        ($($ty:ty),*) => { $(value.is::<$ty>())||* };
+    }
+    any_of!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize)
+}
+
+/// The smallest and biggest value of an integer, `u128`s only go up to `i128::MAX`.
+fn integer_range(value: &dyn Reflect) -> Option<(i128, i128)> {
+    macro_rules! range_of {
+        ($($ty:ty),*) => {
+            $(if value.is::<$ty>() {
+                return Some((
+                    <$ty>::MIN as i128,
+                    i128::try_from(<$ty>::MAX).unwrap_or(i128::MAX),
+                ));
+            })*
+        };
+    }
+    range_of!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+    None
+}
+
+fn get_number(value: &dyn Reflect) -> Option<Number> {
+    macro_rules! get_float {
+        ($($ty:ty),*) => {
+            $(if let Some(value) = value.downcast_ref::<$ty>() {
+                return Some(Number::Float(*value as f64));
+            })*
+        };
+    }
+    macro_rules! get_integer {
+        ($($ty:ty),*) => {
+            $(if let Some(value) = value.downcast_ref::<$ty>() {
+                return Some(Number::Integer(i128::try_from(*value).unwrap_or(i128::MAX)));
+            })*
+        };
+    }
+    get_float!(f32, f64);
+    get_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+    None
+}
+
+fn set_number(value: &mut dyn Reflect, number: Number) {
+    macro_rules! set_float {
+        ($($ty:ty),*) => {
+            $(if let Some(value) = value.downcast_mut::<$ty>() {
+                *value = number.as_f64() as $ty;
+                return;
+            })*
+        };
+    }
+    macro_rules! set_integer {
+        ($($ty:ty),*) => {
+            $(if let Some(value) = value.downcast_mut::<$ty>() {
+                let number = number.as_i128();
+                // integers stop at their min and max
+                *value = <$ty>::try_from(number)
+                    .unwrap_or(if number < 0 { <$ty>::MIN } else { <$ty>::MAX });
+                return;
+            })*
+        };
+    }
+    set_float!(f32, f64);
+    set_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+}
+
+fn euler_degrees(quat: &Quat) -> [f32; 3] {
+    let (x, y, z) = quat.to_euler(EulerRot::XYZ);
+    [x.to_degrees(), y.to_degrees(), z.to_degrees()]
+}
+
+fn read_only_text(value: &dyn Reflect) -> String {
+    let text = if let Some(text) = value.downcast_ref::<String>() {
+        text.clone()
+    } else if let Some(text) = value.downcast_ref::<Cow<'static, str>>() {
+        text.to_string()
+    } else {
+        format!("{value:?}")
+    };
+    // long debug output would push the panel wider
+    match text.char_indices().nth(40) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => text,
+    }
+}
+
+/// The number a numeric field shows.
+fn field_number(value: &dyn Reflect, kind: FieldKind) -> Option<Number> {
+    match kind {
+        FieldKind::Float | FieldKind::Integer => get_number(value),
+        FieldKind::Euler(axis) => value
+            .downcast_ref::<Quat>()
+            // + 0.0 turns -0.0 into 0.0
+            .map(|quat| Number::Float((euler_degrees(quat)[axis] + 0.0) as f64)),
+        FieldKind::ColorChannel(channel) => value
+            .downcast_ref::<Color>()
+            .map(|color| Number::Float(color.as_rgba_f32()[channel] as f64)),
+        _ => None,
+    }
+}
+
+/// The variants an enum's dropdown offers and which one it's on: the ones without fields, and the
+/// current one whatever it is.
+fn enum_options(value: &dyn Reflect) -> Option<(Vec<String>, usize)> {
+    let (Some(TypeInfo::Enum(info)), ReflectRef::Enum(current)) =
+        (value.get_represented_type_info(), value.reflect_ref())
+    else {
+        return None;
+    };
+    let current = current.variant_name();
+    let options: Vec<String> = info
+        .iter()
+        .filter(|variant| matches!(variant, VariantInfo::Unit(_)) || variant.name() == current)
+        .map(|variant| variant.name().to_string())
+        .collect();
+    let selected = options.iter().position(|option| option == current)?;
+    Some((options, selected))
+}
+
+/// Walks everything that decides which fields get spawned, so the inspector knows when to rebuild.
+fn hash_shape(value: &dyn Reflect, hasher: &mut impl Hasher) {
+    value.reflect_type_path().hash(hasher);
+    // these get fixed fields whatever is in them
+    if value.is::<Quat>() || value.is::<Color>() {
+        return;
+    }
+    // a dropdown, which has the current variant in it
+    if let Some((options, _)) = enum_options(value) {
+        options.hash(hasher);
+    }
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => value.iter_fields().for_each(|f| hash_shape(f, hasher)),
+        ReflectRef::TupleStruct(value) => value.iter_fields().for_each(|f| hash_shape(f, hasher)),
+        ReflectRef::Tuple(value) => value.iter_fields().for_each(|f| hash_shape(f, hasher)),
+        ReflectRef::List(value) => value.iter().for_each(|f| hash_shape(f, hasher)),
+        ReflectRef::Array(value) => value.iter().for_each(|f| hash_shape(f, hasher)),
+        ReflectRef::Map(value) => value.len().hash(hasher),
+        ReflectRef::Enum(value) => {
+            value.variant_name().hash(hasher);
+            for field in value.iter_fields() {
+                hash_shape(field.value(), hasher);
+            }
+        }
+        ReflectRef::Value(_) => {}
+    }
+}
+
+/// The entity's components in the order they're shown, with their type if it's reflected.
+fn inspected_components<'w>(
+    world: &'w World,
+    entity: EntityRef<'w>,
+    registry: &TypeRegistry,
+) -> Vec<(String, Option<(TypeId, &'w dyn Reflect)>)> {
+    entity
+        .archetype()
+        .components()
+        .filter_map(|id| world.components().get_info(id))
+        .map(|info| {
+            let reflected = info.type_id().and_then(|type_id| {
+                let reflect_component = registry.get_type_data::<ReflectComponent>(type_id)?;
+                Some((type_id, reflect_component.reflect(entity)?))
+            });
+            (get_short_name(info.name()), reflected)
+        })
+        .collect()
+}
+
+/// Rebuilds the inspector when something else gets selected or the selected entity's components
+/// change shape.
+pub fn rebuild_inspector(
+    world: &World,
+    mut commands: Commands,
+    roots: Query<(Entity, &InspectorRoot)>,
+    selection: Res<Selection>,
+    theme: Res<EditorTheme>,
+) {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let entity = selection.entity.and_then(|entity| world.get_entity(entity));
+    let components = entity.map(|entity| inspected_components(world, entity, &registry));
+    let shown = entity.zip(components.as_ref()).map(|(entity, components)| {
+        let mut hasher = DefaultHasher::new();
+        for (name, reflected) in components {
+            name.hash(&mut hasher);
+            if let Some((_, value)) = reflected {
+                hash_shape(*value, &mut hasher);
+            }
+        }
+        (entity.id(), hasher.finish())
+    });
+
+    for (root, inspector) in &roots {
+        if inspector.built && inspector.shown == shown {
+            continue;
+        }
+        commands
+            .entity(root)
+            .despawn_descendants()
+            .insert(InspectorRoot { built: true, shown })
+            .with_children(|builder| {
+                let (Some(entity), Some(components)) = (entity, &components) else {
+                    builder.spawn(TextBundle::from_section(
+                        "Nothing selected",
+                        TextStyle {
+                            color: Color::WHITE.with_a(0.5),
+                            ..field_style(&theme)
+                        },
+                    ));
+                    return;
+                };
+                for (name, reflected) in components {
+                    let Some((type_id, value)) = reflected else {
+                        builder.spawn(TextBundle::from_section(
+                            format!("{name} (not reflected)"),
+                            TextStyle {
+                                color: Color::WHITE.with_a(0.5),
+                                ..field_style(&theme)
+                            },
+                        ));
+                        continue;
+                    };
+                    let field = InspectorField {
+                        entity: entity.id(),
+                        component: *type_id,
+                        path: String::new(),
+                        kind: FieldKind::ReadOnly,
+                    };
+                    // open or closed the same for every entity with the component
+                    let collapsible = Collapsible::default().with_id(format!("inspector/{name}"));
+                    spawn_nested_collapsible(
+                        builder,
+                        name,
+                        theme.font.clone(),
+                        collapsible,
+                        |builder| {
+                            if !spawn_entries(builder, &theme, &field, *value) {
+                                spawn_value(builder, &theme, &field, "value", *value);
+                            }
+                        },
+                    )
+                    .insert(InspectorComponent {
+                        entity: entity.id(),
+                        component: *type_id,
+                    });
+                }
+                spawn_add_component_button(builder, &theme, entity.id());
+            });
+    }
+}
+
+/// Spawns the fields of a struct, tuple, list or enum. Returns false for anything else.
+fn spawn_entries(
+    builder: &mut ChildBuilder,
+    theme: &EditorTheme,
+    field: &InspectorField,
+    value: &dyn Reflect,
+) -> bool {
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => {
+            for index in 0..value.field_len() {
+                let name = value.name_at(index).unwrap_or_default();
+                let child = field.join(&format!(".{name}"), FieldKind::ReadOnly);
+                spawn_value(builder, theme, &child, name, value.field_at(index).unwrap());
+            }
+        }
+        ReflectRef::TupleStruct(value) => {
+            for (index, item) in value.iter_fields().enumerate() {
+                let child = field.join(&format!(".{index}"), FieldKind::ReadOnly);
+                spawn_value(builder, theme, &child, &index.to_string(), item);
+            }
+        }
+        ReflectRef::Tuple(value) => {
+            for (index, item) in value.iter_fields().enumerate() {
+                let child = field.join(&format!(".{index}"), FieldKind::ReadOnly);
+                spawn_value(builder, theme, &child, &index.to_string(), item);
+            }
+        }
+        ReflectRef::List(value) => {
+            for (index, item) in value.iter().enumerate() {
+                let child = field.join(&format!("[{index}]"), FieldKind::ReadOnly);
+                spawn_value(builder, theme, &child, &index.to_string(), item);
+            }
+        }
+        ReflectRef::Array(value) => {
+            for (index, item) in value.iter().enumerate() {
+                let child = field.join(&format!("[{index}]"), FieldKind::ReadOnly);
+                spawn_value(builder, theme, &child, &index.to_string(), item);
+            }
+        }
+        ReflectRef::Enum(value) => {
+            spawn_row(builder, theme, "variant", |builder| {
+                spawn_field(
+                    builder,
+                    theme,
+                    field.join("", FieldKind::Enum),
+                    value.as_reflect(),
+                );
+            });
+            for (index, item) in value.iter_fields().enumerate() {
+                let name = item
+                    .name()
+                    .map_or_else(|| index.to_string(), str::to_string);
+                let child = field.join(&format!(".{name}"), FieldKind::ReadOnly);
+                spawn_value(builder, theme, &child, &name, item.value());
+            }
+        }
+        ReflectRef::Map(_) | ReflectRef::Value(_) => return false,
+    }
+    true
+}
+
+/// Spawns a labelled row for a value, or a label with the value's own fields under it.
+fn spawn_value(
+    builder: &mut ChildBuilder,
+    theme: &EditorTheme,
+    field: &InspectorField,
+    label: &str,
+    value: &dyn Reflect,
+) {
+    // number fields next to each other, for vectors and the like
+    let inline = match value.reflect_ref() {
+        ReflectRef::Struct(value) => {
+            (1..=4).contains(&value.field_len())
+                && value.iter_fields().all(|f| is_float(f) || is_integer(f))
+        }
+        _ => false,
+    };
+
+    if value.is::<Quat>() {
+        let axes: Vec<_> = (0..3)
+            .map(|axis| number_field(value, FieldKind::Euler(axis)))
+            .collect();
+        spawn_row(builder, theme, label, |builder| {
+            spawn_vector_field(builder, theme, &axes, |axis, commands| {
+                commands.insert(field.join("", FieldKind::Euler(axis)));
+            });
+        });
+    } else if value.is::<Color>() {
+        spawn_row(builder, theme, label, |builder| {
+            spawn_field(builder, theme, field.join("", FieldKind::Color), value);
+            for channel in 0..4 {
+                let kind = FieldKind::ColorChannel(channel);
+                spawn_field(builder, theme, field.join("", kind), value);
+            }
+        });
+    } else if inline {
+        let ReflectRef::Struct(value) = value.reflect_ref() else {
+            return;
+        };
+        // vectors, with the axes colored
+        let is_vector = (0..value.field_len())
+            .all(|index| value.name_at(index) == ["x", "y", "z", "w"].get(index).copied());
+        spawn_row(builder, theme, label, |builder| {
+            if is_vector {
+                let axes: Vec<_> = value
+                    .iter_fields()
+                    .map(|item| number_field(item, field_kind(item)))
+                    .collect();
+                spawn_vector_field(builder, theme, &axes, |index, commands| {
+                    let item = value.field_at(index).unwrap();
+                    let name = value.name_at(index).unwrap_or_default();
+                    commands.insert(field.join(&format!(".{name}"), field_kind(item)));
+                });
+                return;
+            }
+            for (index, item) in value.iter_fields().enumerate() {
+                let name = value.name_at(index).unwrap_or_default();
+                let kind = field_kind(item);
+                spawn_field(builder, theme, field.join(&format!(".{name}"), kind), item);
+            }
+        });
+    } else if matches!(value.reflect_ref(), ReflectRef::Enum(value) if value.field_len() == 0) {
+        spawn_row(builder, theme, label, |builder| {
+            spawn_field(builder, theme, field.join("", FieldKind::Enum), value);
+        });
+    } else if is_float(value) || is_integer(value) || value.is::<bool>() {
+        spawn_row(builder, theme, label, |builder| {
+            spawn_field(builder, theme, field.join("", field_kind(value)), value);
+        });
+    } else if matches!(
+        value.reflect_ref(),
+        ReflectRef::Map(_) | ReflectRef::Value(_)
+    ) {
+        spawn_row(builder, theme, label, |builder| {
+            spawn_field(builder, theme, field.join("", FieldKind::ReadOnly), value);
+        });
+    } else {
+        builder.spawn(TextBundle::from_section(label, field_style(theme)));
+        builder
+            .spawn(NodeBundle {
+                style: Style {
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Column,
+                    margin: UiRect::left(Val::Px(14.4)),
+                    row_gap: Val::Px(2.4),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|builder| {
+                spawn_entries(builder, theme, field, value);
+            });
+    }
+}
+
+fn spawn_row(
+    builder: &mut ChildBuilder,
+    theme: &EditorTheme,
+    label: &str,
+    spawn_fields: impl FnOnce(&mut ChildBuilder),
+) {
+    builder
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(4.8),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|builder| {
+            builder.spawn(TextBundle {
+                text: Text::from_section(label, field_style(theme)),
+                style: Style {
+                    min_width: Val::Px(80.0),
+                    ..default()
+                },
+                ..default()
+            });
+            spawn_fields(builder);
+        });
+}
+
+/// The kind of field for a number or a bool.
+fn field_kind(value: &dyn Reflect) -> FieldKind {
+    if is_float(value) {
+        FieldKind::Float
+    } else if is_integer(value) {
+        FieldKind::Integer
+    } else {
+        FieldKind::Bool
+    }
+}
+
+/// A numeric field taking whatever kind of number `value` is.
+fn number_field(value: &dyn Reflect, kind: FieldKind) -> NumericField {
+    let number = field_number(value, kind).unwrap_or_default();
+    let field = match kind {
+        FieldKind::Integer => match integer_range(value) {
+            Some((min, max)) => NumericField::integer(0, min, max),
+            None => NumericField::i32(0),
+        },
+        FieldKind::Euler(_) => NumericField::f32(0.0).with_step(0.5),
+        FieldKind::ColorChannel(_) => NumericField::f32(0.0).with_range(0.0, 1.0),
+        _ => NumericField::f32(0.0),
+    };
+    field.with_value(number)
+}
+
+fn spawn_field(
+    builder: &mut ChildBuilder,
+    theme: &EditorTheme,
+    field: InspectorField,
+    value: &dyn Reflect,
+) {
+    match field.kind {
+        FieldKind::Float
+        | FieldKind::Integer
+        | FieldKind::Euler(_)
+        | FieldKind::ColorChannel(_) => {
+            spawn_numeric_field(builder, theme, number_field(value, field.kind)).insert(field);
+        }
+        FieldKind::Bool => {
+            let checked = value.downcast_ref::<bool>().copied().unwrap_or_default();
+            spawn_checkbox(builder, theme, checked).insert(field);
+        }
+        FieldKind::Enum => {
+            let (options, selected) = enum_options(value).unwrap_or_default();
+            spawn_dropdown(builder, theme, Dropdown { options, selected }).insert(field);
+        }
+        FieldKind::Color => {
+            let color = value.downcast_ref::<Color>().copied().unwrap_or_default();
+            spawn_color_field(builder, color).insert(field);
+        }
+        FieldKind::ReadOnly => {
+            builder.spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        read_only_text(value),
+                        TextStyle {
+                            color: Color::WHITE.with_a(0.5),
+                            ..field_style(theme)
+                        },
+                    ),
+                    style: Style {
+                        min_width: Val::Px(56.0),
+                        padding: UiRect::axes(Val::Px(4.8), Val::Px(1.2)),
+                        ..default()
+                    },
+                    background_color: BackgroundColor(theme.input_background),
+                    ..default()
+                },
+                field,
+            ));
+        }
+    }
+}
+
+/// Keeps the fields showing what's in the world, for when something else changes it.
+pub fn refresh_inspector_fields(
+    world: &World,
+    mut commands: Commands,
+    fields: Query<(
+        Entity,
+        &InspectorField,
+        Option<&Text>,
+        Option<&NumericField>,
+        Option<&Checkbox>,
+        Option<&Dropdown>,
+        Option<&ColorField>,
+    )>,
+    editing: Query<(), With<TextInput>>,
+) {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    for (entity, field, text, numeric, checkbox, dropdown, color) in &fields {
+        let Some(value) = read_field(world, &registry, field) else {
+            continue;
+        };
+        if let Some(numeric) = numeric {
+            // don't change it under someone typing into it
+            if editing.contains(entity) {
+                continue;
+            }
+            if let Some(number) = field_number(value, field.kind).map(|n| numeric.clamp(n)) {
+                if numeric.value != number {
+                    commands.entity(entity).insert(NumericField {
+                        value: number,
+                        ..*numeric
+                    });
+                }
+            }
+        } else if let Some(checkbox) = checkbox {
+            if let Some(checked) = value.downcast_ref::<bool>() {
+                if checkbox.checked != *checked {
+                    commands
+                        .entity(entity)
+                        .insert(Checkbox { checked: *checked });
+                }
+            }
+        } else if let Some(dropdown) = dropdown {
+            // the options only change along with the shape, which rebuilds everything
+            if let Some((_, selected)) = enum_options(value) {
+                if dropdown.selected != selected {
+                    commands.entity(entity).insert(Dropdown {
+                        selected,
+                        ..dropdown.clone()
+                    });
+                }
+            }
+        } else if let Some(field) = color {
+            if let Some(color) = value.downcast_ref::<Color>() {
+                if field.color != *color {
+                    commands.entity(entity).insert(ColorField { color: *color });
+                }
+            }
+        } else if let Some(text) = text {
+            let value = read_only_text(value);
+            if text.sections[0].value != value {
+                let mut text = text.clone();
+                text.sections[0].value = value;
+                commands.entity(entity).insert(text);
+            }
+        }
+    }
+}
+
+/// Turns what's changed with the fields' widgets into [`InspectorEdit`]s.
+pub fn interact_inspector_fields(
+    mut numbers: EventReader<ValueChanged>,
+    mut checkboxes: EventReader<CheckboxChanged>,
+    mut dropdowns: EventReader<DropdownChanged>,
+    mut colors: EventReader<ColorChanged>,
+    fields: Query<&InspectorField>,
+    mut edits: EventWriter<InspectorEdit>,
+) {
+    let changes = numbers
+        .read()
+        .map(|change| (change.entity, FieldChange::Set(change.value)))
+        .chain(
+            checkboxes
+                .read()
+                .map(|change| (change.entity, FieldChange::SetBool(change.checked))),
+        )
+        .chain(dropdowns.read().map(|change| {
+            (
+                change.entity,
+                FieldChange::SetVariant(change.option.clone()),
+            )
+        }))
+        .chain(
+            colors
+                .read()
+                .map(|change| (change.entity, FieldChange::SetColor(change.color))),
+        );
+    for (entity, change) in changes {
+        if let Ok(field) = fields.get(entity) {
+            edits.send(InspectorEdit {
+                field: field.clone(),
+                change,
+            });
+        }
+    }
+}
+
+/// Writes [`InspectorEdit`]s back to the entities they're for.
+pub fn apply_inspector_edits(world: &mut World) {
+    let edits: Vec<_> = world
+        .resource_mut::<Events<InspectorEdit>>()
+        .drain()
+        .collect();
+    if edits.is_empty() {
+        return;
+    }
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    for InspectorEdit { field, change } in edits {
+        let Some(reflect_component) = registry.get_type_data::<ReflectComponent>(field.component)
+        else {
+            continue;
+        };
+        let Some(mut entity) = world.get_entity_mut(field.entity) else {
+            continue;
+        };
+        let Some(mut component) = reflect_component.reflect_mut(&mut entity) else {
+            continue;
+        };
+        let Ok(value) = component.reflect_path_mut(field.path.as_str()) else {
+            continue;
+        };
+        edit_value(value, field.kind, &change);
+    }
+}
+
+/// `color` in the same color space as `like`, so editing a linear color keeps it linear.
+fn keep_color_space(color: Color, like: Color) -> Color {
+    match like {
+        Color::RgbaLinear { .. } => color.as_rgba_linear(),
+        Color::Hsla { .. } => color.as_hsla(),
+        Color::Lcha { .. } => color.as_lcha(),
+        Color::Rgba { .. } => color,
+    }
+}
+
+fn edit_value(value: &mut dyn Reflect, kind: FieldKind, change: &FieldChange) {
+    match (kind, change) {
+        (FieldKind::Float | FieldKind::Integer, FieldChange::Set(number)) => {
+            set_number(value, *number)
+        }
+        (FieldKind::Bool, FieldChange::SetBool(checked)) => {
+            if let Some(value) = value.downcast_mut::<bool>() {
+                *value = *checked;
+            }
+        }
+        (FieldKind::Enum, FieldChange::SetVariant(name)) => set_variant(value, name),
+        (FieldKind::Euler(axis), FieldChange::Set(degrees)) => {
+            if let Some(quat) = value.downcast_mut::<Quat>() {
+                let mut angles = euler_degrees(quat);
+                angles[axis] = degrees.as_f64() as f32;
+                let [x, y, z] = angles.map(f32::to_radians);
+                *quat = Quat::from_euler(EulerRot::XYZ, x, y, z);
+            }
+        }
+        (FieldKind::ColorChannel(channel), FieldChange::Set(amount)) => {
+            if let Some(color) = value.downcast_mut::<Color>() {
+                let mut rgba = color.as_rgba_f32();
+                rgba[channel] = (amount.as_f64() as f32).clamp(0.0, 1.0);
+                *color = keep_color_space(Color::rgba(rgba[0], rgba[1], rgba[2], rgba[3]), *color);
+            }
+        }
+        (FieldKind::Color, FieldChange::SetColor(new)) => {
+            if let Some(color) = value.downcast_mut::<Color>() {
+                *color = keep_color_space(*new, *color);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Switches an enum to the variant called `name`, if it doesn't have fields.
+fn set_variant(value: &mut dyn Reflect, name: &str) {
+    let Some(TypeInfo::Enum(info)) = value.get_represented_type_info() else {
+        return;
+    };
+    let is_current =
+        matches!(value.reflect_ref(), ReflectRef::Enum(value) if value.variant_name() == name);
+    if let (Some(VariantInfo::Unit(variant)), false) = (info.variant(name), is_current) {
+        value.apply(&DynamicEnum::new(variant.name(), DynamicVariant::Unit));
+    }
+}
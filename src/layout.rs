@@ -0,0 +1,245 @@
+use bevy::prelude::*;
+use bevy_mod_picking::PickableBundle;
+
+use crate::camera::{spawn_view_cube, spawn_viewport_camera, CameraSpeedText, Viewport};
+use crate::dock::DockRoot;
+use crate::menu::MenuBarRoot;
+use crate::scene::{EditorOnly, EditorScene};
+use crate::theme::EditorTheme;
+use crate::widgets::spawn_nested_text_bundle;
+
+pub fn spawn_layout(mut commands: Commands, theme: Res<EditorTheme>) {
+    let font = theme.font.clone();
+    // draws the UI, the viewport camera draws over it
+    commands.spawn((
+        Camera3dBundle {
+            transform: Transform::from_xyz(10.0, 10., -5.0).looking_at(Vec3::ZERO, Vec3::Y),
+            ..default()
+        },
+        Name::new("Editor UI Camera"),
+        EditorOnly,
+    ));
+
+    // Top-level flex (app frame), everything under it gets marked EditorOnly too
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    // Use the CSS Flex algorithm for laying out this node
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Column,
+                    // Make node fill the entirety it's parent (in this case the window)
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    max_width: Val::Percent(100.0),
+                    max_height: Val::Percent(100.0),
+                    align_items: AlignItems::FlexStart,
+                    justify_content: JustifyContent::SpaceBetween,
+                    padding: UiRect::all(Val::Px(6.0)),
+                    row_gap: Val::Px(6.0),
+                    ..default()
+                },
+                background_color: BackgroundColor(theme.window_background),
+                ..default()
+            },
+            Name::new("Editor UI"),
+            EditorOnly,
+        ))
+        .with_children(|builder| {
+            // App
+            builder
+                .spawn(NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(100.0),
+                        min_height: Val::Px(0.0),
+                        row_gap: Val::Px(6.0),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|builder| {
+                    // header panel, empty
+                    builder
+                        .spawn(NodeBundle {
+                            style: Style {
+                                display: Display::Flex,
+                                padding: UiRect {
+                                    left: Val::Px(9.0),
+                                    right: Val::Px(9.0),
+                                    top: Val::Px(6.0),
+                                    bottom: Val::Px(6.0),
+                                },
+                                flex_direction: FlexDirection::Row,
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::FlexStart,
+                                position_type: PositionType::Relative,
+                                height: Val::Px(42.0),
+                                flex_shrink: 0.0,
+                                ..default()
+                            },
+                            background_color: BackgroundColor(theme.panel_background),
+                            ..default()
+                        })
+                        .with_children(|builder| {
+                            builder.spawn((
+                                NodeBundle {
+                                    style: Style {
+                                        width: Val::Px(30.0),
+                                        height: Val::Px(30.0),
+                                        margin: UiRect::right(Val::Px(9.6)),
+                                        // no object-fit: contain :(
+                                        ..default()
+                                    },
+                                    // a `NodeBundle` is transparent by default, so to see the image we have to its color to `WHITE`
+                                    background_color: Color::WHITE.into(),
+                                    ..default()
+                                },
+                                UiImage::new(theme.icon.clone()),
+                            ));
+                            // File Edit View Window Help, filled in from the MenuBar, see menu.rs
+                            builder.spawn((
+                                NodeBundle {
+                                    style: Style {
+                                        display: Display::Flex,
+                                        flex_direction: FlexDirection::Row,
+                                        align_items: AlignItems::Center,
+                                        justify_content: JustifyContent::FlexStart,
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                MenuBarRoot,
+                            ));
+                        });
+
+                    // everything under the header comes from the DockTree, see dock.rs
+                    builder.spawn((
+                        NodeBundle {
+                            style: Style {
+                                display: Display::Flex,
+                                flex_direction: FlexDirection::Row,
+                                width: Val::Percent(100.0),
+                                flex_grow: 1.0,
+                                min_height: Val::Px(0.0),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        DockRoot,
+                    ));
+                });
+
+            // Footer / status bar
+            builder
+                .spawn(NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::SpaceBetween,
+                        width: Val::Percent(100.0),
+                        position_type: PositionType::Relative,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|builder| {
+                    // left element is just a display:flex align-center flex-start with gap of 6px
+                    builder
+                        .spawn(NodeBundle {
+                            style: Style {
+                                display: Display::Flex,
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::FlexStart,
+                                row_gap: Val::Px(6.0),
+                                column_gap: Val::Px(6.0),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|builder| {
+                            spawn_nested_text_bundle(
+                                builder,
+                                font.clone(),
+                                "Bevy Editor Super Super Basic Prototype - \
+                                 it isnt really usable yet but it has a viewport and stuff",
+                            );
+                        });
+                    // right element is the active viewport camera's fly speed, filled in by update_camera_speed_text
+                    spawn_nested_text_bundle(builder, font.clone(), "").insert(CameraSpeedText);
+                });
+        });
+
+    spawn_viewport_camera(&mut commands, 0);
+}
+
+/// Spawns the node the camera of the viewport `index` renders into.
+pub fn spawn_viewport_panel(builder: &mut ChildBuilder, theme: &EditorTheme, index: usize) {
+    // viewport content, fills up everything with margin 6px, nothing in it tho its just a background color
+    builder
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    height: Val::Percent(100.0),
+                    width: Val::Percent(100.0),
+                    display: Display::Flex,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::FlexStart,
+                    ..default()
+                },
+                background_color: BackgroundColor(theme.viewport_background),
+                ..default()
+            },
+            Viewport { index },
+        ))
+        .with_children(|builder| spawn_view_cube(builder, theme, index));
+}
+
+/// Spawns the cube, circular base and light under the [`EditorScene`] so there's something to look
+/// at in the viewport.
+pub fn spawn_demo_scene(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    scene: Query<Entity, With<EditorScene>>,
+) {
+    let Ok(scene) = scene.get_single() else {
+        return;
+    };
+    commands.entity(scene).with_children(|builder| {
+        // circular base
+        builder.spawn((
+            PbrBundle {
+                mesh: meshes.add(shape::Circle::new(4.0).into()),
+                material: materials.add(Color::WHITE.into()),
+                transform: Transform::from_rotation(Quat::from_rotation_x(
+                    -std::f32::consts::FRAC_PI_2,
+                )),
+                ..default()
+            },
+            PickableBundle::default(),
+        ));
+        // cube
+        builder.spawn((
+            PbrBundle {
+                mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+                material: materials.add(Color::rgb_u8(124, 144, 255).into()),
+                transform: Transform::from_xyz(0.0, 0.5, 0.0),
+                ..default()
+            },
+            PickableBundle::default(),
+        ));
+        // light
+        builder.spawn(PointLightBundle {
+            point_light: PointLight {
+                intensity: 1500.0,
+                shadows_enabled: true,
+                ..default()
+            },
+            transform: Transform::from_xyz(4.0, 8.0, 4.0),
+            ..default()
+        });
+    });
+}
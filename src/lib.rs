@@ -0,0 +1,361 @@
+//! a prototype Bevy Editor in `bevy_ui`
+//!
+//! add [`EditorPlugin`] to any Bevy 0.12 app (after `DefaultPlugins`) to get the editor layout,
+//! the viewport camera and picking:
+//!
+//! ```no_run
+//! use bevy::prelude::*;
+//! use bevy_editor_real::{EditorPlugin, EditorSettings};
+//!
+//! App::new()
+//!     .add_plugins(DefaultPlugins)
+//!     .add_plugins(EditorPlugin::new(EditorSettings::default().with_demo_scene(false)))
+//!     .run();
+//! ```
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use bevy_mod_picking::DefaultPickingPlugins;
+
+pub mod camera;
+pub mod dock;
+pub mod floating;
+pub mod hierarchy;
+pub mod inspector;
+pub mod layout;
+pub mod menu;
+pub mod panel;
+pub mod presets;
+pub mod scene;
+pub mod search;
+pub mod selection;
+pub mod splitter;
+pub mod tabs;
+pub mod theme;
+pub mod widgets;
+pub mod workspace;
+
+pub use camera::{ActiveViewport, CameraPivot, NavigationScheme, Viewport, ViewportCamera};
+pub use dock::{DockTree, PanelId};
+pub use menu::{EditorMenuAppExt, MenuAction, MenuBar, MenuItem};
+pub use panel::{EditorPanel, EditorPanelAppExt, PanelDescriptor, PanelRegistry};
+pub use presets::WorkspacePresets;
+pub use scene::{EditorOnly, EditorScene, ShowInternals};
+pub use selection::Selection;
+pub use theme::EditorTheme;
+
+/// Adds the editor to an app. Configure it with [`EditorSettings`].
+#[derive(Default)]
+pub struct EditorPlugin {
+    pub settings: EditorSettings,
+}
+
+impl EditorPlugin {
+    pub fn new(settings: EditorSettings) -> Self {
+        Self { settings }
+    }
+}
+
+impl Plugin for EditorPlugin {
+    fn build(&self, app: &mut App) {
+        if self.settings.add_picking_plugins {
+            app.add_plugins(DefaultPickingPlugins);
+        }
+
+        app.insert_resource(self.settings.clone())
+            .init_resource::<EditorTheme>()
+            .init_resource::<DockTree>()
+            .init_resource::<PanelRegistry>()
+            .init_resource::<WorkspacePresets>()
+            .init_resource::<MenuBar>()
+            .init_resource::<menu::OpenMenus>()
+            .add_event::<MenuAction>()
+            .init_resource::<tabs::TabDrag>()
+            .init_resource::<widgets::CollapsibleStates>()
+            .init_resource::<hierarchy::HierarchyState>()
+            .init_resource::<hierarchy::HierarchyDrag>()
+            .init_resource::<ShowInternals>()
+            .init_resource::<Selection>()
+            .insert_resource(self.settings.navigation)
+            .init_resource::<ActiveViewport>()
+            .add_event::<camera::CameraGrabbed>()
+            .add_event::<inspector::InspectorEdit>()
+            .add_event::<inspector::components::ComponentEdit>()
+            .init_resource::<inspector::components::ComponentClipboard>()
+            .add_event::<widgets::numeric::ValueChanged>()
+            .init_resource::<widgets::text_input::TextInputFocus>()
+            .init_non_send_resource::<widgets::text_input::EditorClipboard>()
+            .add_event::<widgets::text_input::TextInputChanged>()
+            .add_event::<widgets::text_input::TextInputSubmitted>()
+            .add_event::<widgets::text_input::TextInputCancelled>()
+            .add_event::<widgets::checkbox::CheckboxChanged>()
+            .add_event::<widgets::dropdown::DropdownChanged>()
+            .add_event::<widgets::color::ColorChanged>()
+            .register_editor_panel(PanelDescriptor::new(
+                PanelId::HIERARCHY,
+                "Hierarchy",
+                hierarchy::spawn_hierarchy_panel,
+            ))
+            .register_editor_panel(PanelDescriptor::new(
+                PanelId::VIEWPORT,
+                "Viewport",
+                |builder, theme| layout::spawn_viewport_panel(builder, theme, 0),
+            ))
+            .register_editor_panel(PanelDescriptor::new(
+                PanelId::INSPECTOR,
+                "Inspector",
+                inspector::spawn_inspector_panel,
+            ))
+            .register_editor_panel(PanelDescriptor::new(
+                PanelId::FEATURES,
+                "Features",
+                |_, _| {},
+            ))
+            .register_editor_panel(PanelDescriptor::new(PanelId::ASSETS, "Assets", |_, _| {}))
+            // before Startup so the demo scene can go under it
+            .add_systems(PreStartup, scene::spawn_editor_scene)
+            .add_systems(Startup, (layout::spawn_layout, tabs::spawn_drop_preview))
+            // in First so whatever Update spawned under the editor's nodes is marked before the
+            // hierarchy looks at it
+            .add_systems(First, scene::mark_editor_entities)
+            // after Startup so the viewport camera exists, and before the dock is first built
+            .add_systems(PostStartup, workspace::load_workspace)
+            .add_systems(Last, workspace::save_workspace)
+            .add_systems(
+                PreUpdate,
+                floating::pick_floating_windows
+                    .in_set(bevy_mod_picking::backend::prelude::PickSet::Backend),
+            )
+            .add_systems(
+                Update,
+                (
+                    (
+                        floating::follow_floating_windows,
+                        floating::sync_floating_windows,
+                        dock::rebuild_dock.run_if(
+                            resource_changed::<DockTree>()
+                                .or_else(resource_changed::<PanelRegistry>()),
+                        ),
+                    )
+                        .chain(),
+                    splitter::drag_splitters,
+                    splitter::reset_splitters,
+                    splitter::highlight_splitters,
+                    tabs::click_tabs,
+                    (
+                        menu::sync_window_menu.run_if(
+                            resource_changed::<WorkspacePresets>()
+                                .or_else(resource_changed::<PanelRegistry>()),
+                        ),
+                        menu::rebuild_menu_bar.run_if(resource_changed::<MenuBar>()),
+                        menu::click_menus,
+                        menu::spawn_menus.run_if(resource_changed::<menu::OpenMenus>()),
+                    )
+                        .chain(),
+                    menu::highlight_menus,
+                    menu::trigger_menu_shortcuts,
+                    menu::handle_menu_actions,
+                    (presets::handle_preset_actions, presets::submit_preset_names)
+                        .after(widgets::text_input::type_into_text_inputs),
+                    (
+                        tabs::start_tab_drag,
+                        tabs::update_tab_drag,
+                        tabs::end_tab_drag,
+                    )
+                        .chain(),
+                    (
+                        hierarchy::edit_hierarchy_search,
+                        hierarchy::update_hierarchy_search_field,
+                        hierarchy::update_hierarchy,
+                        // the rows it spawned have to exist to be filtered
+                        apply_deferred,
+                        hierarchy::filter_hierarchy,
+                        hierarchy::reveal_hierarchy_matches,
+                        hierarchy::toggle_hierarchy_rows,
+                        hierarchy::select_hierarchy_rows,
+                        hierarchy::highlight_selected_rows,
+                    )
+                        .chain(),
+                    (
+                        hierarchy::start_hierarchy_drag,
+                        hierarchy::update_hierarchy_drag,
+                        hierarchy::end_hierarchy_drag,
+                    )
+                        .chain(),
+                    // before everything that reacts to what was typed
+                    (
+                        widgets::text_input::focus_text_inputs,
+                        widgets::text_input::type_into_text_inputs,
+                    )
+                        .chain()
+                        .before(hierarchy::edit_hierarchy_search)
+                        .before(widgets::numeric::click_numeric_fields),
+                    (
+                        widgets::numeric::click_numeric_fields,
+                        widgets::numeric::drag_numeric_fields,
+                        widgets::numeric::edit_numeric_fields,
+                        widgets::numeric::update_numeric_fields,
+                    )
+                        .chain()
+                        .before(inspector::interact_inspector_fields),
+                    (
+                        widgets::popup::close_popups,
+                        widgets::checkbox::toggle_checkboxes,
+                        widgets::dropdown::click_dropdowns,
+                        widgets::popup::highlight_popup_entries,
+                        widgets::color::click_color_fields,
+                        widgets::color::drag_color_pickers,
+                        widgets::color::slide_color_pickers
+                            .after(widgets::numeric::drag_numeric_fields),
+                        widgets::color::update_color_fields,
+                        widgets::color::update_color_pickers,
+                    )
+                        .chain()
+                        .before(inspector::interact_inspector_fields),
+                    (
+                        inspector::components::open_component_menus
+                            .after(widgets::popup::close_popups)
+                            .after(widgets::text_input::focus_text_inputs),
+                        apply_deferred,
+                        inspector::components::update_add_component_lists,
+                        inspector::components::click_component_menus,
+                    )
+                        .chain()
+                        .before(inspector::components::apply_component_edits),
+                    (
+                        selection::forget_despawned_selection,
+                        selection::select_picked,
+                        inspector::interact_inspector_fields,
+                        inspector::apply_inspector_edits,
+                        inspector::components::apply_component_edits,
+                        inspector::rebuild_inspector,
+                        // the fields that were just despawned can't be refreshed
+                        apply_deferred,
+                        inspector::refresh_inspector_fields,
+                    )
+                        .chain(),
+                    widgets::toggle_collapsibles,
+                    (
+                        camera::spawn_viewport_cameras,
+                        camera::update_viewport_cameras,
+                        camera::update_camera,
+                        camera::click_view_cube,
+                        camera::frame_selected,
+                        camera::snap_camera_views,
+                        camera::animate_camera_transitions,
+                        camera::update_camera_speed_text,
+                        camera::update_view_cube,
+                    )
+                        .chain(),
+                ),
+            )
+            // after Update so collapsibles spawned during it show the right state on their first
+            // frame
+            .add_systems(
+                PostUpdate,
+                (
+                    widgets::update_collapsibles,
+                    widgets::text_input::update_text_inputs,
+                    widgets::checkbox::update_checkboxes,
+                    widgets::dropdown::update_dropdowns,
+                )
+                    .before(bevy::ui::UiSystem::Layout),
+            );
+
+        // the extra viewports of the quad view, their cameras get spawned when they're first shown
+        for index in 1..camera::VIEWPORT_COUNT {
+            app.register_editor_panel(PanelDescriptor::new(
+                PanelId::viewport(index),
+                format!("Viewport {}", index + 1),
+                move |builder, theme| layout::spawn_viewport_panel(builder, theme, index),
+            ));
+        }
+
+        if self.settings.demo_scene {
+            app.add_systems(Startup, layout::spawn_demo_scene);
+        }
+
+        floating::add_render_systems(app);
+    }
+}
+
+/// Settings for [`EditorPlugin`], available as a resource once the plugin is added.
+#[derive(Resource, Clone, Debug)]
+pub struct EditorSettings {
+    /// adds `DefaultPickingPlugins`, turn this off if your app already adds them
+    pub add_picking_plugins: bool,
+    /// spawns the cube, circular base and light so the viewport has something in it
+    pub demo_scene: bool,
+    /// font used by all editor text, relative to the assets folder
+    pub font: String,
+    /// image shown at the left of the header, relative to the assets folder
+    pub icon: String,
+    /// which mouse buttons orbit and pan the viewport camera, until it's changed from the View menu
+    pub navigation: NavigationScheme,
+    /// where the layout, camera and so on get saved on exit and restored from on startup,
+    /// relative to the project directory. `None` turns that off
+    pub workspace_file: Option<PathBuf>,
+}
+
+impl Default for EditorSettings {
+    fn default() -> Self {
+        Self {
+            add_picking_plugins: true,
+            demo_scene: true,
+            font: "fonts/Inter-Regular.ttf".to_string(),
+            icon: "icon.png".to_string(),
+            navigation: NavigationScheme::default(),
+            workspace_file: Some(PathBuf::from(".bevy_editor/workspace.ron")),
+        }
+    }
+}
+
+impl EditorSettings {
+    pub fn with_picking_plugins(mut self, add_picking_plugins: bool) -> Self {
+        self.add_picking_plugins = add_picking_plugins;
+        self
+    }
+
+    pub fn with_demo_scene(mut self, demo_scene: bool) -> Self {
+        self.demo_scene = demo_scene;
+        self
+    }
+
+    pub fn with_font(mut self, font: impl Into<String>) -> Self {
+        self.font = font.into();
+        self
+    }
+
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = icon.into();
+        self
+    }
+
+    pub fn with_navigation(mut self, navigation: NavigationScheme) -> Self {
+        self.navigation = navigation;
+        self
+    }
+
+    pub fn with_workspace_file(mut self, workspace_file: impl Into<PathBuf>) -> Self {
+        self.workspace_file = Some(workspace_file.into());
+        self
+    }
+
+    /// Don't save or restore the workspace, every launch starts from the default layout.
+    pub fn without_workspace_file(mut self) -> Self {
+        self.workspace_file = None;
+        self
+    }
+
+    /// [`Self::workspace_file`] resolved against the project directory, which is
+    /// `CARGO_MANIFEST_DIR` under `cargo run` and the working directory otherwise.
+    pub fn workspace_path(&self) -> Option<PathBuf> {
+        let file = self.workspace_file.as_ref()?;
+        let project_dir = std::env::var_os("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .or_else(|| std::env::current_dir().ok())?;
+        Some(project_dir.join(file))
+    }
+}
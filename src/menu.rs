@@ -0,0 +1,1069 @@
+//! the menu bar in the header. menus are plain data in the [`MenuBar`] resource, picking an item
+//! (or pressing its shortcut) sends a [`MenuAction`] event, and whatever cares about that action
+//! handles it.
+//!
+//! plugins can add their own items, anywhere in the existing menus or in new ones:
+//!
+//! ```no_run
+//! use bevy::prelude::*;
+//! use bevy_editor_real::menu::{EditorMenuAppExt, MenuAction, MenuItem, MenuShortcut};
+//! use bevy_editor_real::EditorPlugin;
+//!
+//! const BAKE_LIGHTING: MenuAction = MenuAction::Custom(std::borrow::Cow::Borrowed("bake"));
+//!
+//! App::new()
+//!     .add_plugins((DefaultPlugins, EditorPlugin::default()))
+//!     .add_editor_menu_item(
+//!         "Tools/Bake Lighting",
+//!         MenuItem::from(BAKE_LIGHTING)
+//!             .with_shortcut(MenuShortcut::ctrl(KeyCode::B))
+//!             .enabled_if(|world| world.contains_resource::<AmbientLight>()),
+//!     )
+//!     .add_editor_menu_item(
+//!         "File/Export Scene",
+//!         MenuItem::from(MenuAction::Custom("export".into())).after("Save Workspace"),
+//!     )
+//!     .add_systems(Update, |mut actions: EventReader<MenuAction>| {
+//!         for action in actions.read() {
+//!             if *action == BAKE_LIGHTING {
+//!                 info!("baking");
+//!             }
+//!         }
+//!     })
+//!     .run();
+//! ```
+use std::borrow::Cow;
+use std::fmt;
+
+use bevy::app::AppExit;
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use bevy_mod_picking::prelude::*;
+
+use crate::camera::{AxisView, NavigationScheme};
+use crate::dock::{DockTree, PanelId};
+use crate::panel::PanelRegistry;
+use crate::presets::WorkspacePresets;
+use crate::scene::{EditorOnly, ShowInternals};
+use crate::selection::Selection;
+use crate::theme::EditorTheme;
+use crate::widgets::text_input::TextInputFocus;
+
+/// Something a menu item does. Sent as an event when the item is picked.
+#[derive(Event, Clone, Debug, PartialEq)]
+pub enum MenuAction {
+    SaveWorkspace,
+    Quit,
+    Undo,
+    Redo,
+    /// puts the layout back to the preset it came from
+    ResetLayout,
+    ApplyPreset(String),
+    /// asks for a name and saves the current layout as a new preset
+    SavePreset,
+    /// asks for a new name for a saved preset
+    RenamePreset(String),
+    DeletePreset(String),
+    /// opens the panel if it's closed, and closes it if it's open
+    TogglePanel(PanelId),
+    /// shows or hides the editor's own entities in the hierarchy and so on
+    ToggleInternals,
+    SetNavigation(NavigationScheme),
+    /// moves the viewport camera to show the selected entity
+    FrameSelected,
+    /// turns the viewport camera to look along an axis
+    SetView(AxisView),
+    /// switches the viewport camera between perspective and orthographic
+    ToggleOrthographic,
+    About,
+    /// for menu items added by other plugins, which handle their own actions
+    Custom(Cow<'static, str>),
+}
+
+/// A key combination shown next to a menu item, which also picks it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MenuShortcut {
+    pub key: KeyCode,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl MenuShortcut {
+    pub const fn new(key: KeyCode) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            shift: false,
+            alt: false,
+        }
+    }
+
+    pub const fn ctrl(key: KeyCode) -> Self {
+        Self {
+            ctrl: true,
+            ..Self::new(key)
+        }
+    }
+
+    pub const fn with_shift(self) -> Self {
+        Self {
+            shift: true,
+            ..self
+        }
+    }
+
+    pub const fn with_alt(self) -> Self {
+        Self { alt: true, ..self }
+    }
+
+    /// Whether the key was just pressed with exactly these modifiers held.
+    pub fn just_pressed(&self, input: &Input<KeyCode>) -> bool {
+        let held = |left, right| input.any_pressed([left, right]);
+        input.just_pressed(self.key)
+            && held(KeyCode::ControlLeft, KeyCode::ControlRight) == self.ctrl
+            && held(KeyCode::ShiftLeft, KeyCode::ShiftRight) == self.shift
+            && held(KeyCode::AltLeft, KeyCode::AltRight) == self.alt
+    }
+}
+
+impl fmt::Display for MenuShortcut {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+        // number keys are called Key1, Key2 and so on
+        let key = format!("{:?}", self.key);
+        write!(f, "{}", key.strip_prefix("Key").unwrap_or(&key))
+    }
+}
+
+/// Where an item added with [`EditorMenuAppExt::add_editor_menu_item`] goes in its menu.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum MenuPosition {
+    First,
+    #[default]
+    Last,
+    /// right before the entry with this label, or last if there isn't one
+    Before(Cow<'static, str>),
+    /// right after the entry with this label, or last if there isn't one
+    After(Cow<'static, str>),
+}
+
+/// Decides whether a menu item is enabled or checked, looked at whenever its menu is opened.
+pub type MenuCondition = dyn Fn(&World) -> bool + Send + Sync;
+
+pub struct MenuItem {
+    pub label: String,
+    pub action: MenuAction,
+    pub shortcut: Option<MenuShortcut>,
+    /// `None` means always enabled
+    pub enabled: Option<Box<MenuCondition>>,
+    /// `None` means the item can't be checked at all
+    pub checked: Option<Box<MenuCondition>>,
+    pub position: MenuPosition,
+}
+
+/// An item without a label yet, for [`EditorMenuAppExt::add_editor_menu_item`] which takes the
+/// label from the path.
+impl From<MenuAction> for MenuItem {
+    fn from(action: MenuAction) -> Self {
+        Self::new("", action)
+    }
+}
+
+impl MenuItem {
+    pub fn new(label: impl Into<String>, action: MenuAction) -> Self {
+        Self {
+            label: label.into(),
+            action,
+            shortcut: None,
+            enabled: None,
+            checked: None,
+            position: MenuPosition::Last,
+        }
+    }
+
+    pub fn with_shortcut(mut self, shortcut: MenuShortcut) -> Self {
+        self.shortcut = Some(shortcut);
+        self
+    }
+
+    /// Greys the item out whenever `enabled` returns `false`.
+    pub fn enabled_if(mut self, enabled: impl Fn(&World) -> bool + Send + Sync + 'static) -> Self {
+        self.enabled = Some(Box::new(enabled));
+        self
+    }
+
+    /// Makes the item checkable, with a ✓ next to it whenever `checked` returns `true`.
+    pub fn checked_if(mut self, checked: impl Fn(&World) -> bool + Send + Sync + 'static) -> Self {
+        self.checked = Some(Box::new(checked));
+        self
+    }
+
+    pub fn with_position(mut self, position: MenuPosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn before(self, label: impl Into<Cow<'static, str>>) -> Self {
+        self.with_position(MenuPosition::Before(label.into()))
+    }
+
+    pub fn after(self, label: impl Into<Cow<'static, str>>) -> Self {
+        self.with_position(MenuPosition::After(label.into()))
+    }
+
+    pub fn is_enabled(&self, world: &World) -> bool {
+        self.enabled.as_ref().is_none_or(|enabled| enabled(world))
+    }
+
+    pub fn is_checked(&self, world: &World) -> Option<bool> {
+        self.checked.as_ref().map(|checked| checked(world))
+    }
+}
+
+pub enum MenuEntry {
+    Item(MenuItem),
+    Submenu(Menu),
+    Separator,
+}
+
+impl MenuEntry {
+    pub fn label(&self) -> Option<&str> {
+        match self {
+            Self::Item(item) => Some(&item.label),
+            Self::Submenu(menu) => Some(&menu.label),
+            Self::Separator => None,
+        }
+    }
+}
+
+/// A dropdown, either at the top of the menu bar or as a submenu of another one.
+pub struct Menu {
+    pub label: String,
+    pub entries: Vec<MenuEntry>,
+}
+
+impl Menu {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn item(mut self, item: MenuItem) -> Self {
+        self.entries.push(MenuEntry::Item(item));
+        self
+    }
+
+    pub fn submenu(mut self, menu: Menu) -> Self {
+        self.entries.push(MenuEntry::Submenu(menu));
+        self
+    }
+
+    pub fn separator(mut self) -> Self {
+        self.entries.push(MenuEntry::Separator);
+        self
+    }
+
+    /// The submenu called `label`, added at the end if there isn't one yet.
+    pub fn submenu_mut(&mut self, label: &str) -> &mut Menu {
+        let index = self
+            .entries
+            .iter()
+            .position(|entry| matches!(entry, MenuEntry::Submenu(menu) if menu.label == label))
+            .unwrap_or_else(|| {
+                self.entries.push(MenuEntry::Submenu(Menu::new(label)));
+                self.entries.len() - 1
+            });
+        let MenuEntry::Submenu(menu) = &mut self.entries[index] else {
+            unreachable!()
+        };
+        menu
+    }
+
+    /// Adds an item where its [`MenuPosition`] says, replacing any item with the same label.
+    pub fn insert_item(&mut self, item: MenuItem) {
+        let existing = self.entries.iter().position(
+            |entry| matches!(entry, MenuEntry::Item(existing) if existing.label == item.label),
+        );
+        if let Some(index) = existing {
+            self.entries[index] = MenuEntry::Item(item);
+            return;
+        }
+        let find = |label: &str| {
+            self.entries
+                .iter()
+                .position(|entry| entry.label() == Some(label))
+        };
+        let index = match &item.position {
+            MenuPosition::First => Some(0),
+            MenuPosition::Last => None,
+            MenuPosition::Before(label) => find(label),
+            MenuPosition::After(label) => find(label).map(|index| index + 1),
+        };
+        let index = index.unwrap_or(self.entries.len());
+        self.entries.insert(index, MenuEntry::Item(item));
+    }
+}
+
+/// Every menu in the header, left to right.
+#[derive(Resource)]
+pub struct MenuBar {
+    pub menus: Vec<Menu>,
+}
+
+impl Default for MenuBar {
+    fn default() -> Self {
+        Self {
+            menus: vec![
+                Menu::new("File")
+                    .item(
+                        MenuItem::new("Save Workspace", MenuAction::SaveWorkspace)
+                            .with_shortcut(MenuShortcut::ctrl(KeyCode::S)),
+                    )
+                    .separator()
+                    .item(
+                        MenuItem::new("Quit", MenuAction::Quit)
+                            .with_shortcut(MenuShortcut::ctrl(KeyCode::Q)),
+                    ),
+                // there's no undo history yet
+                Menu::new("Edit")
+                    .item(
+                        MenuItem::new("Undo", MenuAction::Undo)
+                            .with_shortcut(MenuShortcut::ctrl(KeyCode::Z))
+                            .enabled_if(|_| false),
+                    )
+                    .item(
+                        MenuItem::new("Redo", MenuAction::Redo)
+                            .with_shortcut(MenuShortcut::ctrl(KeyCode::Z).with_shift())
+                            .enabled_if(|_| false),
+                    ),
+                Menu::new("View")
+                    .item(MenuItem::new("Reset Layout", MenuAction::ResetLayout))
+                    .separator()
+                    .item(
+                        MenuItem::new("Frame Selected", MenuAction::FrameSelected)
+                            .with_shortcut(MenuShortcut::new(KeyCode::F))
+                            // F is just a letter while typing
+                            .enabled_if(|world| {
+                                world.resource::<Selection>().entity.is_some() && !is_typing(world)
+                            }),
+                    )
+                    .submenu(
+                        Menu::new("Viewpoint")
+                            .item(view_item(
+                                AxisView::Top,
+                                MenuShortcut::new(KeyCode::Numpad7),
+                            ))
+                            .item(view_item(
+                                AxisView::Bottom,
+                                MenuShortcut::ctrl(KeyCode::Numpad7),
+                            ))
+                            .item(view_item(
+                                AxisView::Front,
+                                MenuShortcut::new(KeyCode::Numpad1),
+                            ))
+                            .item(view_item(
+                                AxisView::Back,
+                                MenuShortcut::ctrl(KeyCode::Numpad1),
+                            ))
+                            .item(view_item(
+                                AxisView::Right,
+                                MenuShortcut::new(KeyCode::Numpad3),
+                            ))
+                            .item(view_item(
+                                AxisView::Left,
+                                MenuShortcut::ctrl(KeyCode::Numpad3),
+                            ))
+                            .separator()
+                            .item(
+                                MenuItem::new(
+                                    "Toggle Orthographic",
+                                    MenuAction::ToggleOrthographic,
+                                )
+                                .with_shortcut(MenuShortcut::new(KeyCode::Numpad5))
+                                .enabled_if(|world| !is_typing(world)),
+                            ),
+                    )
+                    .item(
+                        MenuItem::new("Show Editor Internals", MenuAction::ToggleInternals)
+                            .checked_if(|world| world.resource::<ShowInternals>().0),
+                    )
+                    .submenu(
+                        Menu::new("Navigation")
+                            .item(navigation_item(NavigationScheme::Unity))
+                            .item(navigation_item(NavigationScheme::Blender))
+                            .item(navigation_item(NavigationScheme::Fly)),
+                    ),
+                // the submenus get filled in by `sync_window_menu`
+                Menu::new("Window")
+                    .submenu(Menu::new("Workspaces"))
+                    .submenu(Menu::new("Panels")),
+                Menu::new("Help").item(MenuItem::new("About Bevy Editor", MenuAction::About)),
+            ],
+        }
+    }
+}
+
+/// Whether a text field has focus, so shortcuts without Ctrl are just typing.
+fn is_typing(world: &World) -> bool {
+    world.resource::<TextInputFocus>().0.is_some()
+}
+
+fn view_item(view: AxisView, shortcut: MenuShortcut) -> MenuItem {
+    MenuItem::new(view.label(), MenuAction::SetView(view))
+        .with_shortcut(shortcut)
+        .enabled_if(|world| !is_typing(world))
+}
+
+fn navigation_item(scheme: NavigationScheme) -> MenuItem {
+    MenuItem::new(scheme.label(), MenuAction::SetNavigation(scheme))
+        .checked_if(move |world| *world.resource::<NavigationScheme>() == scheme)
+}
+
+impl MenuBar {
+    /// The top level menu called `label`. If there isn't one yet it's added before Help, which
+    /// stays last.
+    pub fn menu_mut(&mut self, label: &str) -> &mut Menu {
+        let index = match self.menus.iter().position(|menu| menu.label == label) {
+            Some(index) => index,
+            None => {
+                let index = self
+                    .menus
+                    .iter()
+                    .position(|menu| menu.label == "Help")
+                    .unwrap_or(self.menus.len());
+                self.menus.insert(index, Menu::new(label));
+                index
+            }
+        };
+        &mut self.menus[index]
+    }
+
+    /// Adds an item at a path like `"Tools/Lighting/Bake"`: the top level menu, then any
+    /// submenus (both made if they don't exist yet), then the item's label.
+    pub fn add_item(&mut self, path: &str, item: impl Into<MenuItem>) -> bool {
+        let segments: Vec<_> = path
+            .split('/')
+            .map(str::trim)
+            .filter(|segment| !segment.is_empty())
+            .collect();
+        let [top, submenus @ .., label] = segments.as_slice() else {
+            return false;
+        };
+        let mut menu = self.menu_mut(top);
+        for submenu in submenus {
+            menu = menu.submenu_mut(submenu);
+        }
+        let mut item = item.into();
+        item.label = label.to_string();
+        menu.insert_item(item);
+        true
+    }
+
+    /// Finds a menu by the index of the top level menu, then the index of each submenu in its
+    /// parent.
+    pub fn menu(&self, path: &[usize]) -> Option<&Menu> {
+        let (&first, rest) = path.split_first()?;
+        let mut menu = self.menus.get(first)?;
+        for &index in rest {
+            let MenuEntry::Submenu(submenu) = menu.entries.get(index)? else {
+                return None;
+            };
+            menu = submenu;
+        }
+        Some(menu)
+    }
+
+    /// Finds an entry by the path to its menu followed by its index in there.
+    pub fn entry(&self, path: &[usize]) -> Option<&MenuEntry> {
+        let (&index, menu) = path.split_last()?;
+        self.menu(menu)?.entries.get(index)
+    }
+
+    /// Every item, including the ones in submenus.
+    pub fn items(&self) -> impl Iterator<Item = &MenuItem> {
+        fn collect<'a>(entries: &'a [MenuEntry], items: &mut Vec<&'a MenuItem>) {
+            for entry in entries {
+                match entry {
+                    MenuEntry::Item(item) => items.push(item),
+                    MenuEntry::Submenu(menu) => collect(&menu.entries, items),
+                    MenuEntry::Separator => {}
+                }
+            }
+        }
+
+        let mut items = Vec::new();
+        for menu in &self.menus {
+            collect(&menu.entries, &mut items);
+        }
+        items.into_iter()
+    }
+}
+
+/// Which menus are open, and where their dropdowns go.
+#[derive(Resource, Default, Debug)]
+pub struct OpenMenus {
+    /// the open top level menu, then the open submenu in each dropdown
+    pub path: Vec<usize>,
+    /// the top left corner of each dropdown, in ui pixels
+    pub positions: Vec<Vec2>,
+}
+
+impl OpenMenus {
+    /// Opens the menu at `index` in the dropdown at `depth`, closing anything deeper.
+    fn open(&mut self, depth: usize, index: usize, position: Vec2) {
+        self.path.truncate(depth);
+        self.positions.truncate(depth);
+        self.path.push(index);
+        self.positions.push(position);
+    }
+
+    fn is_open(&self) -> bool {
+        !self.path.is_empty()
+    }
+}
+
+/// The node the menu bar buttons are spawned into.
+#[derive(Component)]
+pub struct MenuBarRoot;
+
+#[derive(Component, Debug)]
+pub struct MenuBarButton {
+    pub menu: usize,
+}
+
+#[derive(Component)]
+pub struct MenuDropdown;
+
+#[derive(Component, Debug)]
+pub struct MenuEntryButton {
+    /// see [`MenuBar::entry`]
+    pub path: Vec<usize>,
+    pub enabled: bool,
+}
+
+pub fn rebuild_menu_bar(
+    mut commands: Commands,
+    menu_bar: Res<MenuBar>,
+    roots: Query<Entity, With<MenuBarRoot>>,
+    mut open_menus: ResMut<OpenMenus>,
+    theme: Res<EditorTheme>,
+) {
+    // the paths of whatever is open might not point at the same things anymore
+    if open_menus.is_open() {
+        *open_menus = OpenMenus::default();
+    }
+    for root in &roots {
+        commands
+            .entity(root)
+            .despawn_descendants()
+            .with_children(|builder| {
+                for (index, menu) in menu_bar.menus.iter().enumerate() {
+                    builder
+                        .spawn((
+                            NodeBundle {
+                                style: Style {
+                                    padding: UiRect::axes(Val::Px(6.0), Val::Px(2.4)),
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            MenuBarButton { menu: index },
+                        ))
+                        .with_children(|builder| {
+                            spawn_menu_text(builder, &theme, &menu.label, Color::WHITE);
+                        });
+                }
+            });
+    }
+}
+
+/// Opens, closes and picks from menus.
+pub fn click_menus(
+    mut clicks: EventReader<Pointer<Click>>,
+    mut overs: EventReader<Pointer<Over>>,
+    bar_buttons: Query<(&MenuBarButton, &Node, &GlobalTransform)>,
+    entries: Query<(&MenuEntryButton, &Node, &GlobalTransform)>,
+    dropdowns: Query<(), With<MenuDropdown>>,
+    menu_bar: Res<MenuBar>,
+    mut open_menus: ResMut<OpenMenus>,
+    mut actions: EventWriter<MenuAction>,
+    keys: Res<Input<KeyCode>>,
+    ui_scale: Res<UiScale>,
+) {
+    let scale = ui_scale.0 as f32;
+    // where the dropdown for a bar button goes
+    let below = |node: &Node, transform: &GlobalTransform| {
+        let rect = node.logical_rect(transform);
+        Vec2::new(rect.min.x, rect.max.y + 8.4) / scale
+    };
+    // where a submenu goes
+    let beside = |node: &Node, transform: &GlobalTransform| {
+        let rect = node.logical_rect(transform);
+        Vec2::new(rect.max.x, rect.min.y - 4.8) / scale
+    };
+
+    for click in clicks.read() {
+        if click.button != PointerButton::Primary {
+            continue;
+        }
+        if let Ok((button, node, transform)) = bar_buttons.get(click.target) {
+            if open_menus.path.first() == Some(&button.menu) {
+                *open_menus = OpenMenus::default();
+            } else {
+                open_menus.open(0, button.menu, below(node, transform));
+            }
+        } else if let Ok((entry, node, transform)) = entries.get(click.target) {
+            match menu_bar.entry(&entry.path) {
+                Some(MenuEntry::Item(item)) if entry.enabled => {
+                    actions.send(item.action.clone());
+                    *open_menus = OpenMenus::default();
+                }
+                Some(MenuEntry::Submenu(_)) if entry.enabled => {
+                    let (&index, menu) = entry.path.split_last().unwrap();
+                    open_menus.open(menu.len(), index, beside(node, transform));
+                }
+                // separators and disabled items don't do anything
+                _ => {}
+            }
+        } else if !dropdowns.contains(click.target) && open_menus.is_open() {
+            *open_menus = OpenMenus::default();
+        }
+    }
+
+    if !open_menus.is_open() {
+        overs.clear();
+        return;
+    }
+    for over in overs.read() {
+        if let Ok((button, node, transform)) = bar_buttons.get(over.target) {
+            // moving along the bar while a menu is open switches menus
+            if open_menus.path.first() != Some(&button.menu) {
+                open_menus.open(0, button.menu, below(node, transform));
+            }
+        } else if let Ok((entry, node, transform)) = entries.get(over.target) {
+            let (&index, menu) = entry.path.split_last().unwrap();
+            let depth = menu.len();
+            match menu_bar.entry(&entry.path) {
+                Some(MenuEntry::Submenu(_)) if entry.enabled => {
+                    if open_menus.path.get(depth) != Some(&index) {
+                        open_menus.open(depth, index, beside(node, transform));
+                    }
+                }
+                // hovering anything else in a dropdown closes the submenus it opened
+                _ => {
+                    if open_menus.path.len() > depth {
+                        open_menus.path.truncate(depth);
+                        open_menus.positions.truncate(depth);
+                    }
+                }
+            }
+        }
+    }
+
+    if keys.just_pressed(KeyCode::Escape) {
+        *open_menus = OpenMenus::default();
+    }
+}
+
+/// Respawns the dropdowns of whatever menus are open, checking the state of each item.
+pub fn spawn_menus(
+    world: &World,
+    mut commands: Commands,
+    dropdowns: Query<Entity, With<MenuDropdown>>,
+) {
+    for dropdown in &dropdowns {
+        commands.entity(dropdown).despawn_recursive();
+    }
+    let menu_bar = world.resource::<MenuBar>();
+    let open_menus = world.resource::<OpenMenus>();
+    let theme = world.resource::<EditorTheme>();
+    for depth in 0..open_menus.path.len() {
+        let path = &open_menus.path[..=depth];
+        let Some(menu) = menu_bar.menu(path) else {
+            continue;
+        };
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(open_menus.positions[depth].x),
+                        top: Val::Px(open_menus.positions[depth].y),
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Column,
+                        padding: UiRect::vertical(Val::Px(4.8)),
+                        min_width: Val::Px(220.0),
+                        ..default()
+                    },
+                    background_color: BackgroundColor(theme.input_background),
+                    z_index: ZIndex::Global(50 + depth as i32),
+                    ..default()
+                },
+                MenuDropdown,
+                EditorOnly,
+            ))
+            .with_children(|builder| {
+                for (index, entry) in menu.entries.iter().enumerate() {
+                    let mut entry_path = path.to_vec();
+                    entry_path.push(index);
+                    match entry {
+                        MenuEntry::Item(item) => spawn_menu_entry(
+                            builder,
+                            theme,
+                            entry_path,
+                            item.is_enabled(world),
+                            item.is_checked(world),
+                            &item.label,
+                            item.shortcut.map(|shortcut| shortcut.to_string()),
+                        ),
+                        MenuEntry::Submenu(submenu) => spawn_menu_entry(
+                            builder,
+                            theme,
+                            entry_path,
+                            !submenu.entries.is_empty(),
+                            None,
+                            &submenu.label,
+                            Some("▶".to_string()),
+                        ),
+                        MenuEntry::Separator => {
+                            builder.spawn((
+                                NodeBundle {
+                                    style: Style {
+                                        height: Val::Px(1.0),
+                                        margin: UiRect::vertical(Val::Px(4.8)),
+                                        ..default()
+                                    },
+                                    background_color: BackgroundColor(theme.window_background),
+                                    ..default()
+                                },
+                                MenuEntryButton {
+                                    path: entry_path,
+                                    enabled: false,
+                                },
+                            ));
+                        }
+                    }
+                }
+            });
+    }
+}
+
+fn spawn_menu_entry(
+    builder: &mut ChildBuilder,
+    theme: &EditorTheme,
+    path: Vec<usize>,
+    enabled: bool,
+    checked: Option<bool>,
+    label: &str,
+    hint: Option<String>,
+) {
+    let color = if enabled {
+        Color::WHITE
+    } else {
+        Color::WHITE.with_a(0.35)
+    };
+    builder
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    padding: UiRect::axes(Val::Px(9.6), Val::Px(4.8)),
+                    column_gap: Val::Px(7.0),
+                    ..default()
+                },
+                ..default()
+            },
+            MenuEntryButton { path, enabled },
+        ))
+        .with_children(|builder| {
+            // a column for checkmarks so the labels line up
+            let check = if checked == Some(true) { "✓" } else { "" };
+            spawn_menu_text(builder, theme, check, color).insert(Style {
+                width: Val::Px(12.0),
+                ..default()
+            });
+            spawn_menu_text(builder, theme, label, color).insert(Style {
+                flex_grow: 1.0,
+                ..default()
+            });
+            if let Some(hint) = hint {
+                spawn_menu_text(builder, theme, &hint, color.with_a(color.a() * 0.5)).insert(
+                    Style {
+                        margin: UiRect::left(Val::Px(24.0)),
+                        ..default()
+                    },
+                );
+            }
+        });
+}
+
+/// Menu text lets the pointer through so clicks land on the button or entry it's in.
+fn spawn_menu_text<'w, 's, 'a>(
+    builder: &'a mut ChildBuilder<'w, 's, '_>,
+    theme: &EditorTheme,
+    text: &str,
+    color: Color,
+) -> EntityCommands<'w, 's, 'a> {
+    builder.spawn((
+        TextBundle::from_section(
+            text,
+            TextStyle {
+                font: theme.font.clone(),
+                font_size: 14.3,
+                color,
+            },
+        ),
+        Pickable::IGNORE,
+    ))
+}
+
+pub fn highlight_menus(
+    mut overs: EventReader<Pointer<Over>>,
+    mut outs: EventReader<Pointer<Out>>,
+    mut entries: Query<(&MenuEntryButton, &mut BackgroundColor), Without<MenuBarButton>>,
+    mut bar_buttons: Query<(&MenuBarButton, &mut BackgroundColor), Without<MenuEntryButton>>,
+    open_menus: Res<OpenMenus>,
+    theme: Res<EditorTheme>,
+) {
+    for over in overs.read() {
+        if let Ok((entry, mut background)) = entries.get_mut(over.target) {
+            if entry.enabled {
+                background.0 = theme.accent;
+            }
+        }
+    }
+    for out in outs.read() {
+        if let Ok((entry, mut background)) = entries.get_mut(out.target) {
+            if entry.enabled {
+                background.0 = Color::NONE;
+            }
+        }
+    }
+    if open_menus.is_changed() {
+        for (button, mut background) in &mut bar_buttons {
+            background.0 = if open_menus.path.first() == Some(&button.menu) {
+                theme.window_background
+            } else {
+                Color::NONE
+            };
+        }
+    }
+}
+
+/// Picks enabled items whose shortcut was just pressed.
+pub fn trigger_menu_shortcuts(world: &World, mut commands: Commands) {
+    let input = world.resource::<Input<KeyCode>>();
+    if input.get_just_pressed().len() == 0 {
+        return;
+    }
+    for item in world.resource::<MenuBar>().items() {
+        let Some(shortcut) = item.shortcut else {
+            continue;
+        };
+        if shortcut.just_pressed(input) && item.is_enabled(world) {
+            let action = item.action.clone();
+            commands.add(move |world: &mut World| world.send_event(action));
+        }
+    }
+}
+
+/// Fills in the Window menu's list of presets and panels.
+pub fn sync_window_menu(
+    mut menu_bar: ResMut<MenuBar>,
+    presets: Res<WorkspacePresets>,
+    panels: Res<PanelRegistry>,
+) {
+    let window = menu_bar.menu_mut("Window");
+
+    let workspaces = window.submenu_mut("Workspaces");
+    workspaces.entries.clear();
+    for preset in &presets.presets {
+        let name = preset.name.clone();
+        workspaces.entries.push(MenuEntry::Item(
+            MenuItem::new(&preset.name, MenuAction::ApplyPreset(name.clone())).checked_if(
+                move |world| {
+                    world.resource::<WorkspacePresets>().current.as_deref() == Some(name.as_str())
+                },
+            ),
+        ));
+    }
+    workspaces.entries.push(MenuEntry::Separator);
+    workspaces.entries.push(MenuEntry::Item(MenuItem::new(
+        "Save Layout as Preset...",
+        MenuAction::SavePreset,
+    )));
+    let saved: Vec<_> = presets.user_presets().map(|preset| &preset.name).collect();
+    if !saved.is_empty() {
+        let mut rename = Menu::new("Rename Preset");
+        let mut delete = Menu::new("Delete Preset");
+        for name in saved {
+            rename = rename.item(MenuItem::new(
+                format!("{name}..."),
+                MenuAction::RenamePreset(name.clone()),
+            ));
+            delete = delete.item(MenuItem::new(name, MenuAction::DeletePreset(name.clone())));
+        }
+        workspaces.entries.push(MenuEntry::Submenu(rename));
+        workspaces.entries.push(MenuEntry::Submenu(delete));
+    }
+
+    let panel_menu = window.submenu_mut("Panels");
+    panel_menu.entries.clear();
+    for panel in panels.iter() {
+        let id = panel.id();
+        panel_menu.entries.push(MenuEntry::Item(
+            MenuItem::new(panel.title(), MenuAction::TogglePanel(id.clone()))
+                .checked_if(move |world| world.resource::<DockTree>().contains(&id)),
+        ));
+    }
+}
+
+/// Handles the actions that don't belong to anything more specific.
+pub fn handle_menu_actions(
+    mut actions: EventReader<MenuAction>,
+    mut exits: EventWriter<AppExit>,
+    mut tree: ResMut<DockTree>,
+    mut show_internals: ResMut<ShowInternals>,
+    mut navigation: ResMut<NavigationScheme>,
+) {
+    for action in actions.read() {
+        match action {
+            MenuAction::Quit => exits.send(AppExit),
+            MenuAction::TogglePanel(panel) => {
+                let closed = tree.remove_panel(panel);
+                if !closed {
+                    tree.open_panel(panel);
+                }
+            }
+            MenuAction::ToggleInternals => show_internals.0 = !show_internals.0,
+            MenuAction::SetNavigation(scheme) => *navigation = *scheme,
+            MenuAction::About => info!(
+                "Bevy Editor {}, a prototype editor built with bevy_ui",
+                env!("CARGO_PKG_VERSION")
+            ),
+            _ => {}
+        }
+    }
+}
+
+pub trait EditorMenuAppExt {
+    /// Adds an item to the menu bar, see [`MenuBar::add_item`]. Pass a [`MenuAction`] directly,
+    /// or a [`MenuItem`] made from one for a shortcut, position or enabled condition.
+    fn add_editor_menu_item(&mut self, path: &str, item: impl Into<MenuItem>) -> &mut Self;
+}
+
+impl EditorMenuAppExt for App {
+    fn add_editor_menu_item(&mut self, path: &str, item: impl Into<MenuItem>) -> &mut Self {
+        self.init_resource::<MenuBar>();
+        if !self.world.resource_mut::<MenuBar>().add_item(path, item) {
+            warn!("{path:?} isn't a menu item path, those look like \"Tools/Bake Lighting\"");
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom(name: &'static str) -> MenuItem {
+        MenuAction::Custom(name.into()).into()
+    }
+
+    fn labels(menu: &Menu) -> Vec<&str> {
+        menu.entries
+            .iter()
+            .map(|entry| entry.label().unwrap_or("---"))
+            .collect()
+    }
+
+    fn menu_bar() -> MenuBar {
+        MenuBar {
+            menus: vec![Menu::new("File"), Menu::new("Help")],
+        }
+    }
+
+    #[test]
+    fn add_item_makes_menus_and_submenus() {
+        let mut menu_bar = menu_bar();
+        assert!(menu_bar.add_item("Tools / Lighting / Bake", custom("bake")));
+        assert!(menu_bar.add_item("Tools/Lighting/Clear", custom("clear")));
+        // new top level menus go before Help
+        let tops: Vec<_> = menu_bar.menus.iter().map(|menu| &menu.label).collect();
+        assert_eq!(tops, ["File", "Tools", "Help"]);
+        let lighting = menu_bar.menu_mut("Tools").submenu_mut("Lighting");
+        assert_eq!(labels(lighting), ["Bake", "Clear"]);
+        let MenuEntry::Item(bake) = &lighting.entries[0] else {
+            panic!("Bake should be an item");
+        };
+        assert_eq!(bake.action, MenuAction::Custom("bake".into()));
+    }
+
+    #[test]
+    fn add_item_needs_a_menu_and_a_label() {
+        let mut menu_bar = menu_bar();
+        assert!(!menu_bar.add_item("", custom("nothing")));
+        assert!(!menu_bar.add_item("Tools", custom("nothing")));
+        assert!(!menu_bar.add_item(" / ", custom("nothing")));
+        assert_eq!(menu_bar.menus.len(), 2);
+    }
+
+    #[test]
+    fn duplicate_items_replace_the_old_one() {
+        let mut menu_bar = menu_bar();
+        menu_bar.add_item("File/Export", custom("first"));
+        menu_bar.add_item("File/Import", custom("import"));
+        // keeps its place even with a different position
+        menu_bar.add_item("File/Export", custom("second").after("Import"));
+        let file = menu_bar.menu_mut("File");
+        assert_eq!(labels(file), ["Export", "Import"]);
+        let MenuEntry::Item(export) = &file.entries[0] else {
+            panic!("Export should be an item");
+        };
+        assert_eq!(export.action, MenuAction::Custom("second".into()));
+    }
+
+    #[test]
+    fn insertion_order_and_positions() {
+        let mut menu = Menu::new("File")
+            .item(MenuItem::new("Save", MenuAction::SaveWorkspace))
+            .separator()
+            .item(MenuItem::new("Quit", MenuAction::Quit));
+        menu.insert_item(MenuItem::new("Last", MenuAction::About));
+        menu.insert_item(
+            MenuItem::new("First", MenuAction::About).with_position(MenuPosition::First),
+        );
+        menu.insert_item(MenuItem::new("Before Quit", MenuAction::About).before("Quit"));
+        menu.insert_item(MenuItem::new("After Save", MenuAction::About).after("Save"));
+        menu.insert_item(MenuItem::new("After Save 2", MenuAction::About).after("Save"));
+        // missing anchors go last
+        menu.insert_item(MenuItem::new("Orphan", MenuAction::About).before("Nope"));
+        assert_eq!(
+            labels(&menu),
+            [
+                "First",
+                "Save",
+                "After Save 2",
+                "After Save",
+                "---",
+                "Before Quit",
+                "Quit",
+                "Last",
+                "Orphan",
+            ]
+        );
+    }
+}
@@ -0,0 +1,141 @@
+//! panels are registered with the editor instead of being spawned by hand. every registered panel
+//! gets the same chrome (tab with title, icon and ×, panel body) and can be docked anywhere in the
+//! [`DockTree`](crate::dock::DockTree).
+//!
+//! ```no_run
+//! use bevy::prelude::*;
+//! use bevy_editor_real::panel::{EditorPanelAppExt, PanelDescriptor};
+//! use bevy_editor_real::widgets::spawn_nested_text_bundle;
+//! use bevy_editor_real::{DockTree, EditorPlugin, PanelId};
+//!
+//! const CONSOLE: PanelId = PanelId::from_static("my_game::console");
+//!
+//! App::new()
+//!     .add_plugins((DefaultPlugins, EditorPlugin::default()))
+//!     .register_editor_panel(PanelDescriptor::new(CONSOLE, "Console", |builder, theme| {
+//!         spawn_nested_text_bundle(builder, theme.font.clone(), "nothing logged yet");
+//!     }))
+//!     .add_systems(Startup, |mut tree: ResMut<DockTree>| {
+//!         tree.open_panel(&CONSOLE);
+//!     })
+//!     .run();
+//! ```
+use bevy::prelude::*;
+
+use crate::dock::PanelId;
+use crate::theme::EditorTheme;
+
+/// Something that can be docked in the editor.
+pub trait EditorPanel: Send + Sync + 'static {
+    fn id(&self) -> PanelId;
+
+    /// The text on the panel's tab.
+    fn title(&self) -> &str;
+
+    /// An image shown on the panel's tab, relative to the assets folder.
+    fn icon(&self) -> Option<&str> {
+        None
+    }
+
+    /// Spawns the panel's content into its body. This runs every time the dock gets rebuilt.
+    fn build(&self, builder: &mut ChildBuilder, theme: &EditorTheme);
+}
+
+type BuildPanel = dyn Fn(&mut ChildBuilder, &EditorTheme) + Send + Sync;
+
+/// An [`EditorPanel`] made out of a title and a function, for when a whole type is overkill.
+pub struct PanelDescriptor {
+    pub id: PanelId,
+    pub title: String,
+    pub icon: Option<String>,
+    pub build: Box<BuildPanel>,
+}
+
+impl PanelDescriptor {
+    pub fn new(
+        id: PanelId,
+        title: impl Into<String>,
+        build: impl Fn(&mut ChildBuilder, &EditorTheme) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            id,
+            title: title.into(),
+            icon: None,
+            build: Box::new(build),
+        }
+    }
+
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+}
+
+impl EditorPanel for PanelDescriptor {
+    fn id(&self) -> PanelId {
+        self.id.clone()
+    }
+
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    fn icon(&self) -> Option<&str> {
+        self.icon.as_deref()
+    }
+
+    fn build(&self, builder: &mut ChildBuilder, theme: &EditorTheme) {
+        (self.build)(builder, theme);
+    }
+}
+
+/// Every panel the dock knows how to spawn, in the order they were registered.
+#[derive(Resource, Default)]
+pub struct PanelRegistry {
+    panels: Vec<Box<dyn EditorPanel>>,
+}
+
+impl PanelRegistry {
+    /// Adds a panel, replacing any panel already registered with the same id.
+    pub fn register(&mut self, panel: impl EditorPanel) {
+        let id = panel.id();
+        match self.panels.iter().position(|existing| existing.id() == id) {
+            Some(index) => self.panels[index] = Box::new(panel),
+            None => self.panels.push(Box::new(panel)),
+        }
+    }
+
+    pub fn get(&self, id: &PanelId) -> Option<&dyn EditorPanel> {
+        self.panels
+            .iter()
+            .find(|panel| panel.id() == *id)
+            .map(|panel| panel.as_ref())
+    }
+
+    pub fn contains(&self, id: &PanelId) -> bool {
+        self.get(id).is_some()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn EditorPanel> {
+        self.panels.iter().map(|panel| panel.as_ref())
+    }
+
+    /// The text on a panel's tab, falling back to its id if nothing is registered for it.
+    pub fn title<'a>(&'a self, id: &'a PanelId) -> &'a str {
+        self.get(id).map_or(id.as_str(), |panel| panel.title())
+    }
+}
+
+pub trait EditorPanelAppExt {
+    /// Registers a panel so it can be docked. It isn't opened until something puts it in the
+    /// [`DockTree`](crate::dock::DockTree).
+    fn register_editor_panel(&mut self, panel: impl EditorPanel) -> &mut Self;
+}
+
+impl EditorPanelAppExt for App {
+    fn register_editor_panel(&mut self, panel: impl EditorPanel) -> &mut Self {
+        self.init_resource::<PanelRegistry>();
+        self.world.resource_mut::<PanelRegistry>().register(panel);
+        self
+    }
+}
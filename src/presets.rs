@@ -0,0 +1,420 @@
+//! named workspace presets, switchable from Window > Workspaces. a few come built in, and the
+//! current arrangement can be saved as a new one under a name typed into a prompt (those get saved
+//! with the workspace, and can be renamed and deleted from the same menu).
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::dock::{DockChild, DockNode, DockRoot, DockTree, PanelId};
+use crate::menu::MenuAction;
+use crate::panel::PanelRegistry;
+use crate::theme::EditorTheme;
+use crate::widgets::popup::{find_popup, spawn_popup, Popup};
+use crate::widgets::text_input::{
+    spawn_text_input, TextInput, TextInputCancelled, TextInputFocus, TextInputSubmitted,
+};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WorkspacePreset {
+    pub name: String,
+    pub dock: DockTree,
+    /// built in presets come from code, so they don't get saved
+    #[serde(skip)]
+    pub builtin: bool,
+}
+
+impl WorkspacePreset {
+    pub fn new(name: impl Into<String>, dock: DockTree) -> Self {
+        Self {
+            name: name.into(),
+            dock,
+            builtin: false,
+        }
+    }
+
+    fn builtin(name: &str, dock: DockTree) -> Self {
+        Self {
+            builtin: true,
+            ..Self::new(name, dock)
+        }
+    }
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct WorkspacePresets {
+    pub presets: Vec<WorkspacePreset>,
+    /// the preset that was applied last, if the layout came from one
+    pub current: Option<String>,
+}
+
+impl Default for WorkspacePresets {
+    fn default() -> Self {
+        Self {
+            presets: vec![
+                WorkspacePreset::builtin("Layout", DockTree::default()),
+                WorkspacePreset::builtin("Modeling", modeling_layout()),
+                WorkspacePreset::builtin("Debug", debug_layout()),
+                WorkspacePreset::builtin("Quad View", quad_view_layout()),
+            ],
+            current: Some("Layout".to_string()),
+        }
+    }
+}
+
+/// big viewport, with the hierarchy and inspector stacked on the right
+fn modeling_layout() -> DockTree {
+    DockTree::new(DockNode::row(vec![
+        DockChild::fr(1.0, DockNode::panel(PanelId::VIEWPORT)),
+        DockChild::px(
+            324.0,
+            DockNode::column(vec![
+                DockChild::fr(1.0, DockNode::panel(PanelId::HIERARCHY)),
+                DockChild::fr(1.0, DockNode::panel(PanelId::INSPECTOR)),
+            ]),
+        ),
+    ]))
+}
+
+/// four viewports in a grid, with the hierarchy and inspector stacked on the right
+fn quad_view_layout() -> DockTree {
+    let viewports = |first: usize| {
+        DockNode::row(vec![
+            DockChild::fr(1.0, DockNode::panel(PanelId::viewport(first))),
+            DockChild::fr(1.0, DockNode::panel(PanelId::viewport(first + 1))),
+        ])
+    };
+    DockTree::new(DockNode::row(vec![
+        DockChild::fr(
+            1.0,
+            DockNode::column(vec![
+                DockChild::fr(1.0, viewports(0)),
+                DockChild::fr(1.0, viewports(2)),
+            ]),
+        ),
+        DockChild::px(
+            324.0,
+            DockNode::column(vec![
+                DockChild::fr(1.0, DockNode::panel(PanelId::HIERARCHY)),
+                DockChild::fr(1.0, DockNode::panel(PanelId::INSPECTOR)),
+            ]),
+        ),
+    ]))
+}
+
+/// everything but the viewport gets more room
+fn debug_layout() -> DockTree {
+    DockTree::new(DockNode::row(vec![
+        DockChild::px(
+            324.0,
+            DockNode::column(vec![
+                DockChild::fr(1.0, DockNode::panel(PanelId::HIERARCHY)),
+                DockChild::fr(1.0, DockNode::panel(PanelId::FEATURES)),
+            ]),
+        ),
+        DockChild::fr(
+            1.0,
+            DockNode::column(vec![
+                DockChild::fr(1.0, DockNode::panel(PanelId::VIEWPORT)),
+                DockChild::px(320.0, DockNode::panel(PanelId::ASSETS)),
+            ]),
+        ),
+        DockChild::px(420.0, DockNode::panel(PanelId::INSPECTOR)),
+    ]))
+}
+
+impl WorkspacePresets {
+    pub fn get(&self, name: &str) -> Option<&WorkspacePreset> {
+        self.presets.iter().find(|preset| preset.name == name)
+    }
+
+    /// The presets that aren't built in, which are the ones worth saving.
+    pub fn user_presets(&self) -> impl Iterator<Item = &WorkspacePreset> {
+        self.presets.iter().filter(|preset| !preset.builtin)
+    }
+
+    /// Adds a preset, replacing any preset with the same name that isn't built in.
+    pub fn insert(&mut self, preset: WorkspacePreset) {
+        match self
+            .presets
+            .iter()
+            .position(|existing| existing.name == preset.name)
+        {
+            Some(index) if !self.presets[index].builtin => self.presets[index] = preset,
+            Some(_) => warn!("can't replace the built in preset {:?}", preset.name),
+            None => self.presets.push(preset),
+        }
+    }
+
+    /// Switches `tree` over to the preset called `name`, leaving out any panels that aren't
+    /// registered.
+    pub fn apply(&mut self, name: &str, tree: &mut DockTree, panels: &PanelRegistry) -> bool {
+        let Some(preset) = self.get(name) else {
+            return false;
+        };
+        let mut dock = preset.dock.clone();
+        dock.retain_panels(|panel| panels.contains(panel));
+        *tree = dock;
+        self.current = Some(name.to_string());
+        true
+    }
+
+    /// An unused name like "Custom 2", what the name prompt starts out with.
+    pub fn unused_name(&self) -> String {
+        (1..)
+            .map(|n| format!("Custom {n}"))
+            .find(|name| self.get(name).is_none())
+            .unwrap()
+    }
+
+    /// Saves `tree` as a preset called `name`, replacing a saved preset with that name. Returns
+    /// `false` if the name is empty or taken by a built in preset.
+    pub fn save_as(&mut self, name: &str, tree: &DockTree) -> bool {
+        let name = name.trim();
+        if name.is_empty() || self.get(name).is_some_and(|preset| preset.builtin) {
+            return false;
+        }
+        self.insert(WorkspacePreset::new(name, tree.clone()));
+        self.current = Some(name.to_string());
+        true
+    }
+
+    /// Renames the saved preset `from`. Built in presets can't be renamed, and names can't be
+    /// empty or taken.
+    pub fn rename(&mut self, from: &str, to: &str) -> bool {
+        let to = to.trim();
+        if to.is_empty() || (to != from && self.get(to).is_some()) {
+            return false;
+        }
+        let Some(preset) = self
+            .presets
+            .iter_mut()
+            .find(|preset| preset.name == from && !preset.builtin)
+        else {
+            return false;
+        };
+        preset.name = to.to_string();
+        if self.current.as_deref() == Some(from) {
+            self.current = Some(to.to_string());
+        }
+        true
+    }
+
+    /// Deletes the saved preset `name`. Built in presets can't be deleted.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let Some(index) = self
+            .presets
+            .iter()
+            .position(|preset| preset.name == name && !preset.builtin)
+        else {
+            return false;
+        };
+        self.presets.remove(index);
+        if self.current.as_deref() == Some(name) {
+            self.current = None;
+        }
+        true
+    }
+}
+
+/// The field of the prompt asking for a preset's name, for saving the layout as a new preset or
+/// for renaming the preset `renaming`.
+#[derive(Component, Clone, Debug)]
+pub struct PresetNameInput {
+    pub renaming: Option<String>,
+}
+
+/// Opens a prompt at the top of the dock asking for a preset's name.
+fn spawn_name_prompt(
+    commands: &mut Commands,
+    theme: &EditorTheme,
+    dock: (Entity, &Node, &GlobalTransform),
+    ui_scale: &UiScale,
+    focus: &mut TextInputFocus,
+    text: &str,
+    renaming: Option<String>,
+) {
+    const WIDTH: f32 = 240.0;
+    let (dock, node, transform) = dock;
+    let rect = node.logical_rect(transform);
+    let position = Vec2::new(rect.center().x - WIDTH / 2.0, rect.min.y + 24.0) / ui_scale.0 as f32;
+    let container = NodeBundle {
+        style: Style {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(4.8),
+            width: Val::Px(WIDTH),
+            ..default()
+        },
+        ..default()
+    };
+    let popup = spawn_popup(commands, theme, dock, position).id();
+    commands
+        .spawn(container)
+        .set_parent(popup)
+        .with_children(|builder| {
+            let label = match &renaming {
+                Some(name) => format!("Rename {name:?} to"),
+                None => "Save layout as".to_string(),
+            };
+            builder.spawn(TextBundle::from_section(
+                label,
+                TextStyle {
+                    font: theme.font.clone(),
+                    font_size: 14.3,
+                    color: Color::WHITE,
+                },
+            ));
+            let mut input = TextInput::new(text).with_placeholder("Preset name");
+            input.select_all();
+            let input = spawn_text_input(builder, theme, input)
+                .insert(PresetNameInput { renaming })
+                .id();
+            focus.0 = Some(input);
+        });
+}
+
+pub fn handle_preset_actions(
+    mut commands: Commands,
+    mut actions: EventReader<MenuAction>,
+    mut presets: ResMut<WorkspacePresets>,
+    mut tree: ResMut<DockTree>,
+    panels: Res<PanelRegistry>,
+    dock: Query<(Entity, &Node, &GlobalTransform), With<DockRoot>>,
+    prompts: Query<Entity, With<PresetNameInput>>,
+    popups: Query<(Entity, &Popup)>,
+    parents: Query<&Parent>,
+    mut focus: ResMut<TextInputFocus>,
+    theme: Res<EditorTheme>,
+    ui_scale: Res<UiScale>,
+) {
+    let mut prompt = |commands: &mut Commands, text: &str, renaming: Option<String>| {
+        // only one prompt at a time
+        for input in &prompts {
+            if let Some(popup) = find_popup(input, &popups, &parents) {
+                commands.entity(popup).despawn_recursive();
+            }
+        }
+        if let Ok(dock) = dock.get_single() {
+            spawn_name_prompt(
+                commands, &theme, dock, &ui_scale, &mut focus, text, renaming,
+            );
+        }
+    };
+    for action in actions.read() {
+        match action {
+            MenuAction::ApplyPreset(name) => {
+                presets.apply(name, &mut tree, &panels);
+            }
+            MenuAction::SavePreset => {
+                let name = presets.unused_name();
+                prompt(&mut commands, &name, None);
+            }
+            MenuAction::RenamePreset(name) => {
+                prompt(&mut commands, name, Some(name.clone()));
+            }
+            MenuAction::DeletePreset(name) => {
+                presets.remove(name);
+            }
+            MenuAction::ResetLayout => {
+                let current = presets.current.clone();
+                if !current.is_some_and(|name| presets.apply(&name, &mut tree, &panels)) {
+                    *tree = DockTree::default();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Saves or renames the preset once Enter is pressed in the name prompt, and closes the prompt.
+/// Clicking away or pressing Escape closes it without doing anything.
+pub fn submit_preset_names(
+    mut commands: Commands,
+    mut submitted: EventReader<TextInputSubmitted>,
+    mut cancelled: EventReader<TextInputCancelled>,
+    inputs: Query<&PresetNameInput>,
+    popups: Query<(Entity, &Popup)>,
+    parents: Query<&Parent>,
+    keys: Res<Input<KeyCode>>,
+    mut focus: ResMut<TextInputFocus>,
+    mut presets: ResMut<WorkspacePresets>,
+    tree: Res<DockTree>,
+) {
+    // inputs get submitted by clicking away too, that's cancelling here
+    let entered = keys.any_just_pressed([KeyCode::Return, KeyCode::NumpadEnter]);
+    let submitted = submitted
+        .read()
+        .filter(|_| entered)
+        .map(|submitted| (submitted.entity, Some(submitted.text.clone())));
+    let cancelled = cancelled.read().map(|cancelled| (cancelled.entity, None));
+    for (entity, text) in submitted.chain(cancelled).collect::<Vec<_>>() {
+        let Ok(input) = inputs.get(entity) else {
+            continue;
+        };
+        if let Some(name) = text {
+            let name = name.trim();
+            let done = match &input.renaming {
+                Some(from) => presets.rename(from, name),
+                None => presets.save_as(name, &tree),
+            };
+            if !done {
+                warn!("can't use {name:?} as a preset name, it's empty or taken");
+                // leave the prompt open to try another one
+                focus.0 = Some(entity);
+                continue;
+            }
+        }
+        if let Some(popup) = find_popup(entity, &popups, &parents) {
+            commands.entity(popup).despawn_recursive();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> DockTree {
+        DockTree::new(DockNode::panel(PanelId::VIEWPORT))
+    }
+
+    #[test]
+    fn save_as() {
+        let mut presets = WorkspacePresets::default();
+        assert_eq!(presets.unused_name(), "Custom 1");
+        assert!(presets.save_as("  Mine ", &tree()));
+        assert_eq!(presets.current.as_deref(), Some("Mine"));
+        assert_eq!(presets.get("Mine").unwrap().dock, tree());
+        assert!(!presets.save_as("", &tree()));
+        assert!(!presets.save_as("Layout", &tree()));
+        // saving over a saved preset replaces it
+        assert!(presets.save_as("Mine", &DockTree::default()));
+        assert_eq!(presets.user_presets().count(), 1);
+        assert_eq!(presets.get("Mine").unwrap().dock, DockTree::default());
+    }
+
+    #[test]
+    fn rename() {
+        let mut presets = WorkspacePresets::default();
+        presets.save_as("Mine", &tree());
+        presets.save_as("Other", &tree());
+        assert!(!presets.rename("Mine", "Other"));
+        assert!(!presets.rename("Mine", "Layout"));
+        assert!(!presets.rename("Mine", " "));
+        assert!(!presets.rename("Layout", "Renamed"));
+        presets.current = Some("Mine".to_string());
+        assert!(presets.rename("Mine", "Renamed"));
+        assert!(presets.get("Mine").is_none());
+        assert_eq!(presets.current.as_deref(), Some("Renamed"));
+    }
+
+    #[test]
+    fn remove() {
+        let mut presets = WorkspacePresets::default();
+        presets.save_as("Mine", &tree());
+        assert!(!presets.remove("Layout"));
+        assert!(presets.remove("Mine"));
+        assert!(!presets.remove("Mine"));
+        assert_eq!(presets.current, None);
+        assert_eq!(presets.user_presets().count(), 0);
+    }
+}
@@ -0,0 +1,47 @@
+//! keeping the editor's own entities apart from the scene being edited. everything the editor spawns
+//! for itself is marked [`EditorOnly`] (UI nodes get it from the root node they're under), and
+//! editor tooling like the hierarchy leaves those out unless [`ShowInternals`] is turned on.
+use bevy::prelude::*;
+
+/// Marks entities that belong to the editor rather than the scene, like its UI and cameras.
+/// Anything saving or listing the scene should skip these. Children of an `EditorOnly` entity get
+/// it too, see [`mark_editor_entities`].
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct EditorOnly;
+
+/// The root of the scene being edited, the demo scene gets spawned under it. Entities the app
+/// spawns itself don't have to be under it to show up in the editor.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct EditorScene;
+
+/// Whether editor tooling shows [`EditorOnly`] entities too. Toggled from View > Show Editor
+/// Internals.
+#[derive(Resource, Clone, Copy, Debug, Default)]
+pub struct ShowInternals(pub bool);
+
+impl ShowInternals {
+    pub fn shows(&self, entity: &EntityRef) -> bool {
+        self.0 || !entity.contains::<EditorOnly>()
+    }
+}
+
+pub fn spawn_editor_scene(mut commands: Commands) {
+    commands.spawn((SpatialBundle::default(), Name::new("Scene"), EditorScene));
+}
+
+/// Marks everything under an [`EditorOnly`] entity, so only the roots the editor spawns need to be
+/// marked by hand.
+pub fn mark_editor_entities(
+    mut commands: Commands,
+    changed: Query<Entity, (With<EditorOnly>, Changed<Children>)>,
+    children: Query<&Children>,
+    unmarked: Query<(), Without<EditorOnly>>,
+) {
+    for parent in &changed {
+        for entity in children.iter_descendants(parent) {
+            if unmarked.contains(entity) {
+                commands.entity(entity).insert(EditorOnly);
+            }
+        }
+    }
+}
@@ -0,0 +1,368 @@
+//! the little query language the hierarchy's search field takes. words match names, `t:Type`
+//! matches entities with a component of that type, and they can be combined:
+//!
+//! - `cube light` both (same as `cube & light` or `cube and light`)
+//! - `cube | light` either (same as `cube or light`)
+//! - `!cube` not (same as `-cube` or `not cube`)
+//! - `t:PointLight | (t:Handle<Mesh> !floor)` grouping
+//! - `"point light"` a name with a space in it
+//!
+//! everything is case insensitive, and it never fails to parse: a missing operand, a missing `)`
+//! or a `t:` without a type just gets left out.
+use std::ops::Range;
+
+use bevy::ecs::component::Components;
+use bevy::prelude::*;
+use bevy::utils::get_short_name;
+
+/// A parsed search, see the module docs for what it looks like.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum SearchQuery {
+    /// nothing was typed, everything matches
+    #[default]
+    All,
+    /// the name contains this, lowercase
+    Name(String),
+    /// it has a component with this short type name, lowercase
+    Type(String),
+    Not(Box<SearchQuery>),
+    And(Vec<SearchQuery>),
+    Or(Vec<SearchQuery>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            '|' => tokens.push(Token::Or),
+            '&' => tokens.push(Token::And),
+            '!' | '-' => tokens.push(Token::Not),
+            '"' => {
+                let word: String = chars.by_ref().take_while(|c| *c != '"').collect();
+                tokens.push(Token::Word(word));
+            }
+            c => {
+                let mut word = String::from(c);
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace() && !"()|&!\"".contains(*c))
+                {
+                    word.push(c);
+                }
+                tokens.push(match word.to_ascii_lowercase().as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    _ => Token::Word(word),
+                });
+            }
+        }
+    }
+    tokens
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    next: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next)
+    }
+
+    fn or(&mut self) -> Option<SearchQuery> {
+        let mut terms: Vec<_> = self.and().into_iter().collect();
+        while self.peek() == Some(&Token::Or) {
+            self.next += 1;
+            terms.extend(self.and());
+        }
+        match terms.len() {
+            0 => None,
+            1 => terms.pop(),
+            _ => Some(SearchQuery::Or(terms)),
+        }
+    }
+
+    fn and(&mut self) -> Option<SearchQuery> {
+        let mut terms = Vec::new();
+        loop {
+            match self.peek() {
+                None | Some(Token::Or) | Some(Token::Close) => break,
+                Some(Token::And) => self.next += 1,
+                Some(_) => terms.extend(self.unary()),
+            }
+        }
+        match terms.len() {
+            0 => None,
+            1 => terms.pop(),
+            _ => Some(SearchQuery::And(terms)),
+        }
+    }
+
+    fn unary(&mut self) -> Option<SearchQuery> {
+        let token = self.peek()?.clone();
+        self.next += 1;
+        match token {
+            Token::Not => self.unary().map(|query| SearchQuery::Not(Box::new(query))),
+            Token::Open => {
+                let query = self.or();
+                if self.peek() == Some(&Token::Close) {
+                    self.next += 1;
+                }
+                query
+            }
+            Token::Word(word) => {
+                let word = word.to_lowercase();
+                match word.strip_prefix("t:") {
+                    // still typing the type
+                    Some("") => None,
+                    Some(ty) => Some(SearchQuery::Type(ty.to_string())),
+                    None => Some(SearchQuery::Name(word)),
+                }
+            }
+            Token::And | Token::Or | Token::Close => None,
+        }
+    }
+}
+
+impl SearchQuery {
+    pub fn parse(text: &str) -> Self {
+        let mut parser = Parser {
+            tokens: tokenize(text),
+            next: 0,
+        };
+        let mut terms = Vec::new();
+        while parser.peek().is_some() {
+            terms.extend(parser.or());
+            // a stray `)`
+            if parser.peek() == Some(&Token::Close) {
+                parser.next += 1;
+            }
+        }
+        match terms.len() {
+            0 => SearchQuery::All,
+            1 => terms.pop().unwrap(),
+            _ => SearchQuery::And(terms),
+        }
+    }
+
+    pub fn is_all(&self) -> bool {
+        *self == SearchQuery::All
+    }
+
+    /// Whether an entity with this label matches. Component types are only looked up if the
+    /// query asks for one.
+    pub fn matches(&self, label: &str, entity: &EntityRef, components: &Components) -> bool {
+        match self {
+            SearchQuery::All => true,
+            SearchQuery::Name(name) => label.to_lowercase().contains(name.as_str()),
+            SearchQuery::Type(ty) => entity.archetype().components().any(|id| {
+                components
+                    .get_info(id)
+                    .is_some_and(|info| get_short_name(info.name()).to_lowercase() == *ty)
+            }),
+            SearchQuery::Not(query) => !query.matches(label, entity, components),
+            SearchQuery::And(queries) => queries
+                .iter()
+                .all(|query| query.matches(label, entity, components)),
+            SearchQuery::Or(queries) => queries
+                .iter()
+                .any(|query| query.matches(label, entity, components)),
+        }
+    }
+
+    /// The names searched for, leaving out the ones under a `!`.
+    fn highlighted_names<'a>(&'a self, names: &mut Vec<&'a str>) {
+        match self {
+            SearchQuery::Name(name) if !name.is_empty() => names.push(name),
+            SearchQuery::And(queries) | SearchQuery::Or(queries) => {
+                for query in queries {
+                    query.highlighted_names(names);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The byte ranges of `label` to highlight, sorted and not overlapping.
+    pub fn highlights(&self, label: &str) -> Vec<Range<usize>> {
+        let mut names = Vec::new();
+        self.highlighted_names(&mut names);
+        // lowercasing can change how many bytes a char takes, so remember which char of the label
+        // each byte of the lowercase one came from
+        let mut lowercase = String::new();
+        let mut chars = Vec::new();
+        for (start, c) in label.char_indices() {
+            for lower in c.to_lowercase() {
+                lowercase.push(lower);
+                chars.resize(lowercase.len(), start..start + c.len_utf8());
+            }
+        }
+        let mut ranges: Vec<Range<usize>> = names
+            .iter()
+            .flat_map(|name| {
+                lowercase
+                    .match_indices(name)
+                    .map(|(start, name)| chars[start].start..chars[start + name.len() - 1].end)
+            })
+            .collect();
+        ranges.sort_by_key(|range| range.start);
+        let mut merged: Vec<Range<usize>> = Vec::new();
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        merged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(name: &str) -> SearchQuery {
+        SearchQuery::Name(name.to_string())
+    }
+
+    fn not(query: SearchQuery) -> SearchQuery {
+        SearchQuery::Not(Box::new(query))
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        use SearchQuery::{And, Or};
+        assert_eq!(
+            SearchQuery::parse("a | b c"),
+            Or(vec![name("a"), And(vec![name("b"), name("c")])])
+        );
+        assert_eq!(
+            SearchQuery::parse("a and b or c"),
+            Or(vec![And(vec![name("a"), name("b")]), name("c")])
+        );
+        assert_eq!(SearchQuery::parse("a & b"), SearchQuery::parse("a b"));
+    }
+
+    #[test]
+    fn not_binds_tightest() {
+        use SearchQuery::{And, Or};
+        assert_eq!(
+            SearchQuery::parse("!a b"),
+            And(vec![not(name("a")), name("b")])
+        );
+        assert_eq!(
+            SearchQuery::parse("-a | b"),
+            Or(vec![not(name("a")), name("b")])
+        );
+        assert_eq!(SearchQuery::parse("not not a"), not(not(name("a"))));
+        assert_eq!(
+            SearchQuery::parse("!(a | b)"),
+            not(Or(vec![name("a"), name("b")]))
+        );
+    }
+
+    #[test]
+    fn parentheses_group() {
+        use SearchQuery::{And, Or};
+        assert_eq!(
+            SearchQuery::parse("(a | b) c"),
+            And(vec![Or(vec![name("a"), name("b")]), name("c")])
+        );
+        assert_eq!(
+            SearchQuery::parse("a (b | (c d))"),
+            And(vec![
+                name("a"),
+                Or(vec![name("b"), And(vec![name("c"), name("d")])]),
+            ])
+        );
+    }
+
+    #[test]
+    fn unbalanced_input_still_parses() {
+        use SearchQuery::{And, Or};
+        assert_eq!(SearchQuery::parse("(a | b"), Or(vec![name("a"), name("b")]));
+        assert_eq!(SearchQuery::parse("a ) b"), And(vec![name("a"), name("b")]));
+        assert_eq!(SearchQuery::parse("))a(("), name("a"));
+        assert_eq!(SearchQuery::parse("| a |"), name("a"));
+        assert_eq!(
+            SearchQuery::parse("a & & b"),
+            And(vec![name("a"), name("b")])
+        );
+        assert_eq!(SearchQuery::parse("!"), SearchQuery::All);
+        assert_eq!(SearchQuery::parse("\"point li"), name("point li"));
+        assert_eq!(SearchQuery::parse("  "), SearchQuery::All);
+    }
+
+    #[test]
+    fn type_terms() {
+        assert_eq!(
+            SearchQuery::parse("T:PointLight"),
+            SearchQuery::Type("pointlight".to_string())
+        );
+        assert_eq!(
+            SearchQuery::parse("!t:Handle<Mesh>"),
+            not(SearchQuery::Type("handle<mesh>".to_string()))
+        );
+        // a bare `t:` isn't a filter yet
+        assert_eq!(SearchQuery::parse("t:"), SearchQuery::All);
+        assert_eq!(SearchQuery::parse("cube t:"), name("cube"));
+    }
+
+    #[test]
+    fn matches_names_and_types() {
+        let mut world = World::new();
+        let entity = world
+            .spawn((Transform::default(), Visibility::default()))
+            .id();
+        let entity = world.entity(entity);
+        let matches = |text: &str, label: &str| {
+            SearchQuery::parse(text).matches(label, &entity, world.components())
+        };
+        assert!(matches("", "Anything"));
+        assert!(matches("CUBE", "Big cube"));
+        assert!(matches("ÉCLAIR", "éclair"));
+        assert!(matches("t:transform", "x"));
+        assert!(!matches("t:PointLight", "x"));
+        assert!(matches("t:pointlight | t:visibility", "x"));
+        assert!(!matches("cube !big", "Big Cube"));
+        assert!(matches("t:", "x"));
+    }
+
+    #[test]
+    fn highlight_ranges() {
+        let highlights = |text: &str, label: &str| -> Vec<(usize, usize)> {
+            SearchQuery::parse(text)
+                .highlights(label)
+                .into_iter()
+                .map(|range| (range.start, range.end))
+                .collect()
+        };
+        assert_eq!(highlights("cube", "Big Cube cube"), [(4, 8), (9, 13)]);
+        // overlapping and touching matches get merged
+        assert_eq!(highlights("cu | ube", "Cube"), [(0, 4)]);
+        assert_eq!(highlights("ab | c", "abc"), [(0, 3)]);
+        // negated and type terms aren't highlighted
+        assert_eq!(
+            highlights("!cube t:Transform light", "Cube light"),
+            [(5, 10)]
+        );
+        assert!(highlights("", "Cube").is_empty());
+        // ranges are byte ranges of the label, even when lowercasing changes the length
+        assert_eq!(highlights("é", "ÉÉ"), [(0, 4)]);
+        assert_eq!(highlights("i", "İx"), [(0, 2)]);
+    }
+}
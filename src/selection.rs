@@ -0,0 +1,36 @@
+//! what's selected in the editor. rows in the hierarchy select their entity when clicked (see
+//! `hierarchy.rs`), and so does clicking something pickable in the viewport.
+use bevy::ecs::entity::Entities;
+use bevy::prelude::*;
+use bevy_mod_picking::prelude::*;
+
+/// The entity the Inspector shows.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Selection {
+    pub entity: Option<Entity>,
+}
+
+/// Selects whatever bevy_mod_picking selected, which is anything with a `PickableBundle` clicked in
+/// the viewport. Its deselections are ignored, it deselects everything whenever the editor's UI is
+/// clicked.
+pub fn select_picked(
+    mut selects: EventReader<Pointer<Select>>,
+    nodes: Query<(), With<Node>>,
+    mut selection: ResMut<Selection>,
+) {
+    for select in selects.read() {
+        if !nodes.contains(select.target) && selection.entity != Some(select.target) {
+            selection.entity = Some(select.target);
+        }
+    }
+}
+
+/// Forgets the selected entity once it's despawned.
+pub fn forget_despawned_selection(entities: &Entities, mut selection: ResMut<Selection>) {
+    if selection
+        .entity
+        .is_some_and(|entity| !entities.contains(entity))
+    {
+        selection.entity = None;
+    }
+}
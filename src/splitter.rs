@@ -0,0 +1,344 @@
+//! the handles between docked panels. drag them to resize the panels on either side, double-click
+//! to put both back to their default sizes.
+use bevy::prelude::*;
+use bevy::window::{CursorIcon, PrimaryWindow};
+use bevy_mod_picking::prelude::*;
+
+use crate::dock::{apply_dock_size, DockAxis, DockChild, DockNode, DockSize, DockTree};
+use crate::theme::EditorTheme;
+
+/// Panels can't be dragged smaller than this, in logical pixels.
+pub const MIN_PANEL_SIZE: f32 = 64.0;
+
+/// Two clicks on a splitter closer together than this (in seconds) reset it.
+const DOUBLE_CLICK_TIME: f32 = 0.4;
+
+#[derive(Component, Debug)]
+pub struct Splitter {
+    /// the path of the split this belongs to in the [`DockTree`]
+    pub split: Vec<usize>,
+    /// the splitter sits between child `index` and child `index + 1` of the split
+    pub index: usize,
+    pub axis: DockAxis,
+}
+
+pub fn spawn_splitter(builder: &mut ChildBuilder, split: Vec<usize>, index: usize, axis: DockAxis) {
+    let (width, height) = match axis {
+        DockAxis::Row => (Val::Px(6.0), Val::Percent(100.0)),
+        DockAxis::Column => (Val::Percent(100.0), Val::Px(6.0)),
+    };
+    builder.spawn((
+        NodeBundle {
+            style: Style {
+                width,
+                height,
+                flex_shrink: 0.0,
+                ..default()
+            },
+            ..default()
+        },
+        Splitter { split, index, axis },
+    ));
+}
+
+/// The dock nodes on either side of a splitter.
+fn neighbours(
+    splitter: Entity,
+    parent: &Parent,
+    children: &Query<&Children>,
+) -> Option<(Entity, Entity)> {
+    let siblings = children.get(parent.get()).ok()?;
+    let index = siblings.iter().position(|sibling| *sibling == splitter)?;
+    Some((
+        *siblings.get(index.checked_sub(1)?)?,
+        *siblings.get(index + 1)?,
+    ))
+}
+
+fn axis_length(node: &Node, axis: DockAxis) -> f32 {
+    match axis {
+        DockAxis::Row => node.size().x,
+        DockAxis::Column => node.size().y,
+    }
+}
+
+/// Moves `delta` pixels from `after` to `before`, without making either smaller than
+/// [`MIN_PANEL_SIZE`]. `before_length` and `after_length` are their current laid out sizes.
+fn resize(
+    before: DockSize,
+    after: DockSize,
+    before_length: f32,
+    after_length: f32,
+    delta: f32,
+) -> (DockSize, DockSize) {
+    let delta = delta.clamp(
+        (MIN_PANEL_SIZE - before_length).min(0.0),
+        (after_length - MIN_PANEL_SIZE).max(0.0),
+    );
+    match (before, after) {
+        (DockSize::Px(before), DockSize::Px(after)) => {
+            (DockSize::Px(before + delta), DockSize::Px(after - delta))
+        }
+        // the fr side just takes up whatever is left over
+        (DockSize::Px(before), after @ DockSize::Fr(_)) => (DockSize::Px(before + delta), after),
+        (before @ DockSize::Fr(_), DockSize::Px(after)) => (before, DockSize::Px(after - delta)),
+        (DockSize::Fr(before), DockSize::Fr(after)) => {
+            let total_length = before_length + after_length;
+            if total_length <= 0.0 {
+                return (DockSize::Fr(before), DockSize::Fr(after));
+            }
+            let total = before + after;
+            let before = total * (before_length + delta) / total_length;
+            (DockSize::Fr(before), DockSize::Fr(total - before))
+        }
+    }
+}
+
+/// Writes new sizes for the children on either side of a splitter to the [`DockTree`] without
+/// triggering a rebuild, the styles get updated in place instead.
+fn set_split_sizes(
+    tree: &mut DockTree,
+    splitter: &Splitter,
+    sizes: impl FnOnce(&DockChild, &DockChild) -> (DockSize, DockSize),
+) -> Option<(DockSize, DockSize)> {
+    let Some(DockNode::Split { children, .. }) = tree.node_mut(&splitter.split) else {
+        return None;
+    };
+    let [before, after] = children.get_mut(splitter.index..=splitter.index + 1)? else {
+        return None;
+    };
+    let (before_size, after_size) = sizes(before, after);
+    before.size = before_size;
+    after.size = after_size;
+    Some((before_size, after_size))
+}
+
+pub fn drag_splitters(
+    mut drags: EventReader<Pointer<Drag>>,
+    splitters: Query<(&Splitter, &Parent)>,
+    children: Query<&Children>,
+    mut nodes: Query<(&Node, &mut Style)>,
+    mut tree: ResMut<DockTree>,
+    ui_scale: Res<UiScale>,
+) {
+    for drag in drags.read() {
+        if drag.button != PointerButton::Primary {
+            continue;
+        }
+        let Ok((splitter, parent)) = splitters.get(drag.target) else {
+            continue;
+        };
+        let Some((before, after)) = neighbours(drag.target, parent, &children) else {
+            continue;
+        };
+        let Ok([(before_node, _), (after_node, _)]) = nodes.get_many([before, after]) else {
+            continue;
+        };
+        let before_length = axis_length(before_node, splitter.axis);
+        let after_length = axis_length(after_node, splitter.axis);
+        // pointer deltas are in window pixels, styles are in ui pixels
+        let delta = match splitter.axis {
+            DockAxis::Row => drag.delta.x,
+            DockAxis::Column => drag.delta.y,
+        } / ui_scale.0 as f32;
+
+        let Some((before_size, after_size)) =
+            set_split_sizes(tree.bypass_change_detection(), splitter, |before, after| {
+                resize(before.size, after.size, before_length, after_length, delta)
+            })
+        else {
+            continue;
+        };
+        if let Ok((_, mut style)) = nodes.get_mut(before) {
+            apply_dock_size(&mut style, before_size, splitter.axis);
+        }
+        if let Ok((_, mut style)) = nodes.get_mut(after) {
+            apply_dock_size(&mut style, after_size, splitter.axis);
+        }
+    }
+}
+
+pub fn reset_splitters(
+    mut clicks: EventReader<Pointer<Click>>,
+    splitters: Query<(&Splitter, &Parent)>,
+    children: Query<&Children>,
+    mut styles: Query<&mut Style>,
+    mut tree: ResMut<DockTree>,
+    time: Res<Time>,
+    mut last_click: Local<Option<(Entity, f32)>>,
+) {
+    for click in clicks.read() {
+        if click.button != PointerButton::Primary {
+            continue;
+        }
+        let Ok((splitter, parent)) = splitters.get(click.target) else {
+            continue;
+        };
+        let now = time.elapsed_seconds();
+        let is_double_click = matches!(
+            *last_click,
+            Some((entity, at)) if entity == click.target && now - at < DOUBLE_CLICK_TIME
+        );
+        if !is_double_click {
+            *last_click = Some((click.target, now));
+            continue;
+        }
+        *last_click = None;
+
+        let Some((before_size, after_size)) =
+            set_split_sizes(tree.bypass_change_detection(), splitter, |before, after| {
+                (before.default_size, after.default_size)
+            })
+        else {
+            continue;
+        };
+        let Some((before, after)) = neighbours(click.target, parent, &children) else {
+            continue;
+        };
+        if let Ok(mut style) = styles.get_mut(before) {
+            apply_dock_size(&mut style, before_size, splitter.axis);
+        }
+        if let Ok(mut style) = styles.get_mut(after) {
+            apply_dock_size(&mut style, after_size, splitter.axis);
+        }
+    }
+}
+
+/// Lights up the splitter under the pointer (or being dragged) and swaps the cursor to a resize one.
+pub fn highlight_splitters(
+    mut over: EventReader<Pointer<Over>>,
+    mut out: EventReader<Pointer<Out>>,
+    mut drag_start: EventReader<Pointer<DragStart>>,
+    mut drag_end: EventReader<Pointer<DragEnd>>,
+    mut splitters: Query<(Entity, &Splitter, &mut BackgroundColor)>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    theme: Res<EditorTheme>,
+    mut hovered: Local<Option<Entity>>,
+    mut dragging: Local<Option<Entity>>,
+) {
+    let mut changed = false;
+    for event in over.read() {
+        if splitters.contains(event.target) {
+            *hovered = Some(event.target);
+            changed = true;
+        }
+    }
+    for event in out.read() {
+        if *hovered == Some(event.target) {
+            *hovered = None;
+            changed = true;
+        }
+    }
+    for event in drag_start.read() {
+        if splitters.contains(event.target) {
+            *dragging = Some(event.target);
+            changed = true;
+        }
+    }
+    for event in drag_end.read() {
+        if *dragging == Some(event.target) {
+            *dragging = None;
+            changed = true;
+        }
+    }
+    if !changed {
+        return;
+    }
+
+    let active = dragging.or(*hovered);
+    let mut icon = CursorIcon::Default;
+    for (entity, splitter, mut background) in &mut splitters {
+        if Some(entity) == active {
+            background.0 = theme.accent;
+            icon = match splitter.axis {
+                DockAxis::Row => CursorIcon::ColResize,
+                DockAxis::Column => CursorIcon::RowResize,
+            };
+        } else {
+            background.0 = Color::NONE;
+        }
+    }
+    if let Ok(mut window) = windows.get_single_mut() {
+        window.cursor.icon = icon;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resize_px() {
+        assert_eq!(
+            resize(DockSize::Px(200.0), DockSize::Px(300.0), 200.0, 300.0, 50.0),
+            (DockSize::Px(250.0), DockSize::Px(250.0))
+        );
+    }
+
+    #[test]
+    fn resize_clamps_to_min_size() {
+        // the after side can only give up what it has above the minimum
+        assert_eq!(
+            resize(
+                DockSize::Px(200.0),
+                DockSize::Px(100.0),
+                200.0,
+                100.0,
+                500.0
+            ),
+            (
+                DockSize::Px(200.0 + 100.0 - MIN_PANEL_SIZE),
+                DockSize::Px(MIN_PANEL_SIZE)
+            )
+        );
+        assert_eq!(
+            resize(
+                DockSize::Px(100.0),
+                DockSize::Px(200.0),
+                100.0,
+                200.0,
+                -500.0
+            ),
+            (
+                DockSize::Px(MIN_PANEL_SIZE),
+                DockSize::Px(200.0 + 100.0 - MIN_PANEL_SIZE)
+            )
+        );
+    }
+
+    #[test]
+    fn resize_below_min_size_only_grows() {
+        // already smaller than the minimum, it can't shrink any further but can still grow
+        assert_eq!(
+            resize(DockSize::Px(40.0), DockSize::Px(300.0), 40.0, 300.0, -10.0),
+            (DockSize::Px(40.0), DockSize::Px(300.0))
+        );
+        assert_eq!(
+            resize(DockSize::Px(40.0), DockSize::Px(300.0), 40.0, 300.0, 10.0),
+            (DockSize::Px(50.0), DockSize::Px(290.0))
+        );
+    }
+
+    #[test]
+    fn resize_fr_keeps_total() {
+        let (DockSize::Fr(before), DockSize::Fr(after)) =
+            resize(DockSize::Fr(1.0), DockSize::Fr(1.0), 300.0, 300.0, 150.0)
+        else {
+            panic!("fr sizes should stay fr");
+        };
+        assert!((before - 1.5).abs() < 1e-5);
+        assert!((after - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn resize_mixed_only_changes_px() {
+        assert_eq!(
+            resize(DockSize::Px(200.0), DockSize::Fr(1.0), 200.0, 400.0, -50.0),
+            (DockSize::Px(150.0), DockSize::Fr(1.0))
+        );
+        assert_eq!(
+            resize(DockSize::Fr(1.0), DockSize::Px(200.0), 400.0, 200.0, -50.0),
+            (DockSize::Fr(1.0), DockSize::Px(250.0))
+        );
+    }
+}
@@ -0,0 +1,374 @@
+//! tab bars. click a tab to show its panel, click the × to close it, drag it along the bar to
+//! reorder, onto another tab bar to stack it there, or onto the edge of another panel to split it.
+//! dropping it anywhere else in the editor window tears it off into its own window, and dragging
+//! that window's tab back onto the dock docks it again.
+use bevy::prelude::*;
+use bevy_mod_picking::prelude::*;
+
+use crate::dock::{DockEdge, DockSpawner, DockTarget, DockTree, PanelId, FLOATING_PANEL_SIZE};
+use crate::floating::PointerWindows;
+use crate::scene::EditorOnly;
+use crate::theme::EditorTheme;
+use crate::widgets::spawn_nested_text_bundle;
+
+/// How close to a panel's edge (as a fraction of its size) a tab has to be dropped to split.
+const EDGE_DROP_ZONE: f32 = 0.25;
+
+/// Where a torn off panel's window is held while it's dragged by its tab, from its top left
+/// corner.
+const FLOATING_GRAB: Vec2 = Vec2::new(40.0, 12.0);
+
+#[derive(Component, Debug)]
+pub struct DockTab {
+    pub panel: PanelId,
+}
+
+/// The × on a tab.
+#[derive(Component, Debug)]
+pub struct DockTabClose {
+    pub panel: PanelId,
+}
+
+#[derive(Component, Debug)]
+pub struct DockTabBar {
+    /// any panel in the bar, to find its tab stack with
+    pub anchor: PanelId,
+}
+
+/// The body of a docked panel, below its tab bar.
+#[derive(Component, Debug)]
+pub struct DockPanelBody {
+    pub panel: PanelId,
+}
+
+/// The UI of a torn off panel, shown in its own [`FloatingWindow`](crate::floating::FloatingWindow).
+#[derive(Component, Debug)]
+pub struct FloatingPanelWindow {
+    pub panel: PanelId,
+}
+
+/// Shows where a dragged tab (or hierarchy row) will end up.
+#[derive(Component)]
+pub struct DropPreview;
+
+/// The tab being dragged right now, and where it would go if it was let go of.
+#[derive(Resource, Default, Debug)]
+pub struct TabDrag {
+    pub panel: Option<PanelId>,
+    pub target: Option<DockTarget>,
+}
+
+pub(crate) fn spawn_tab_bar(
+    builder: &mut ChildBuilder,
+    tabs: &[PanelId],
+    active_panel: &PanelId,
+    spawner: &DockSpawner,
+) {
+    let theme = spawner.theme;
+    builder
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(0.0),
+                    left: Val::Px(0.0),
+                    height: Val::Px(30.),
+                    display: Display::Flex,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::FlexStart,
+                    row_gap: Val::Px(6.0),
+                    column_gap: Val::Px(6.0),
+                    padding: UiRect {
+                        left: Val::Px(6.0),
+                        right: Val::Px(6.0),
+                        top: Val::Px(0.0),
+                        bottom: Val::Px(0.0),
+                    },
+                    ..default()
+                },
+                ..default()
+            },
+            DockTabBar {
+                anchor: active_panel.clone(),
+            },
+        ))
+        .with_children(|builder| {
+            for panel in tabs {
+                // tab
+                builder
+                    .spawn((
+                        NodeBundle {
+                            style: Style {
+                                padding: UiRect {
+                                    left: Val::Px(9.6),
+                                    right: Val::Px(9.6),
+                                    top: Val::Px(0.0),
+                                    bottom: Val::Px(2.4),
+                                },
+                                height: Val::Percent(100.0),
+                                display: Display::Flex,
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::FlexStart,
+                                column_gap: Val::Px(7.0),
+                                ..default()
+                            },
+                            background_color: BackgroundColor(if panel == active_panel {
+                                theme.panel_background
+                            } else {
+                                theme.window_background
+                            }),
+                            ..default()
+                        },
+                        DockTab {
+                            panel: panel.clone(),
+                        },
+                    ))
+                    .with_children(|builder| {
+                        let registered = spawner.panels.get(panel);
+                        if let Some(icon) = registered.and_then(|panel| panel.icon()) {
+                            builder.spawn((
+                                NodeBundle {
+                                    style: Style {
+                                        width: Val::Px(14.0),
+                                        height: Val::Px(14.0),
+                                        ..default()
+                                    },
+                                    background_color: Color::WHITE.into(),
+                                    ..default()
+                                },
+                                UiImage::new(spawner.asset_server.load(icon.to_string())),
+                                Pickable::IGNORE,
+                            ));
+                        }
+                        // the label lets the pointer through so clicks and drags land on the tab
+                        spawn_nested_text_bundle(
+                            builder,
+                            theme.font.clone(),
+                            spawner.panels.title(panel),
+                        )
+                        .insert(Pickable::IGNORE);
+                        spawn_nested_text_bundle(builder, theme.font.clone(), "×").insert(
+                            DockTabClose {
+                                panel: panel.clone(),
+                            },
+                        );
+                    });
+            }
+        });
+}
+
+pub fn spawn_drop_preview(mut commands: Commands, theme: Res<EditorTheme>) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            background_color: BackgroundColor(theme.accent.with_a(0.35)),
+            z_index: ZIndex::Global(100),
+            ..default()
+        },
+        Pickable::IGNORE,
+        DropPreview,
+        EditorOnly,
+    ));
+}
+
+pub fn click_tabs(
+    mut clicks: EventReader<Pointer<Click>>,
+    tabs: Query<&DockTab>,
+    close_buttons: Query<&DockTabClose>,
+    mut tree: ResMut<DockTree>,
+) {
+    for click in clicks.read() {
+        if click.button != PointerButton::Primary {
+            continue;
+        }
+        if let Ok(close) = close_buttons.get(click.target) {
+            tree.remove_panel(&close.panel);
+        } else if let Ok(tab) = tabs.get(click.target) {
+            tree.activate(&tab.panel);
+        }
+    }
+}
+
+pub fn start_tab_drag(
+    mut drag_starts: EventReader<Pointer<DragStart>>,
+    tabs: Query<&DockTab>,
+    mut tab_drag: ResMut<TabDrag>,
+) {
+    for drag_start in drag_starts.read() {
+        if drag_start.button != PointerButton::Primary {
+            continue;
+        }
+        if let Ok(tab) = tabs.get(drag_start.target) {
+            *tab_drag = TabDrag {
+                panel: Some(tab.panel.clone()),
+                target: None,
+            };
+        }
+    }
+}
+
+/// Works out where the dragged tab would land from where the pointer is, and moves the drop
+/// preview there. The pointer stays on the window the drag started in, even over another one, so
+/// this goes by where the docked panels are rather than by what's under the pointer.
+pub fn update_tab_drag(
+    mut drags: EventReader<Pointer<Drag>>,
+    mut tab_drag: ResMut<TabDrag>,
+    tree: Res<DockTree>,
+    tabs: Query<(&DockTab, &Node, &GlobalTransform)>,
+    bars: Query<(&DockTabBar, &Node, &GlobalTransform)>,
+    bodies: Query<(&DockPanelBody, &Node, &GlobalTransform)>,
+    pointer_windows: PointerWindows,
+    mut preview: Query<(&mut Style, &mut BackgroundColor), With<DropPreview>>,
+    ui_scale: Res<UiScale>,
+    theme: Res<EditorTheme>,
+) {
+    let Some(panel) = tab_drag.panel.clone() else {
+        drags.clear();
+        return;
+    };
+    let Some(drag) = drags
+        .read()
+        .filter(|drag| {
+            tabs.get(drag.target)
+                .is_ok_and(|(tab, ..)| tab.panel == panel)
+        })
+        .last()
+    else {
+        return;
+    };
+    let over_own_tab = tabs.get(drag.target).is_ok_and(|(_, node, transform)| {
+        let position = pointer_windows.position(&drag.pointer_location);
+        node.logical_rect(transform).contains(position)
+    });
+    // where the pointer is over the editor window, `None` while that's unknown
+    let pointer = pointer_windows.editor_position(&drag.pointer_location);
+
+    // tabs of a floating panel aren't docked, so there's nothing to dock next to there
+    let docked = |anchor: &PanelId| !tree.is_floating(anchor);
+    let mut hovered = None;
+    if let (Some(pointer), false) = (pointer, over_own_tab) {
+        let tab = tabs.iter().find(|(tab, node, transform)| {
+            docked(&tab.panel) && node.logical_rect(transform).contains(pointer)
+        });
+        let bar = bars.iter().find(|(bar, node, transform)| {
+            docked(&bar.anchor) && node.logical_rect(transform).contains(pointer)
+        });
+        let body = bodies.iter().find(|(body, node, transform)| {
+            docked(&body.panel) && node.logical_rect(transform).contains(pointer)
+        });
+        hovered = if let Some((tab, node, transform)) = tab {
+            let rect = node.logical_rect(transform);
+            let after = pointer.x > rect.center().x;
+            // a thin line on the side of the tab it'd go
+            let x = if after { rect.max.x } else { rect.min.x };
+            let line = Rect::new(x - 1.5, rect.min.y, x + 1.5, rect.max.y);
+            Some((
+                DockTarget::Tab {
+                    anchor: tab.panel.clone(),
+                    after,
+                },
+                line,
+            ))
+        } else if let Some((bar, node, transform)) = bar {
+            Some((
+                DockTarget::Stack {
+                    anchor: bar.anchor.clone(),
+                },
+                node.logical_rect(transform),
+            ))
+        } else if let Some((body, node, transform)) = body {
+            let rect = node.logical_rect(transform);
+            let relative = (pointer - rect.min) / rect.size().max(Vec2::ONE);
+            let distances = [
+                (DockEdge::Left, relative.x),
+                (DockEdge::Right, 1.0 - relative.x),
+                (DockEdge::Top, relative.y),
+                (DockEdge::Bottom, 1.0 - relative.y),
+            ];
+            let (edge, distance) = distances
+                .into_iter()
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .unwrap();
+            let anchor = body.panel.clone();
+            if distance > EDGE_DROP_ZONE {
+                Some((DockTarget::Stack { anchor }, rect))
+            } else {
+                let half = rect.size() / 2.0;
+                let preview = match edge {
+                    DockEdge::Left => Rect::from_corners(rect.min, rect.max - Vec2::X * half.x),
+                    DockEdge::Right => Rect::from_corners(rect.min + Vec2::X * half.x, rect.max),
+                    DockEdge::Top => Rect::from_corners(rect.min, rect.max - Vec2::Y * half.y),
+                    DockEdge::Bottom => Rect::from_corners(rect.min + Vec2::Y * half.y, rect.max),
+                };
+                Some((DockTarget::Edge { anchor, edge }, preview))
+            }
+        } else {
+            None
+        };
+    }
+
+    // rects are in window pixels, styles are in ui pixels
+    let scale = ui_scale.0 as f32;
+    // a floating panel's window gets moved around by its title bar instead
+    if let (None, false, Some(pointer), Some(editor_size)) = (
+        &hovered,
+        over_own_tab || tree.is_floating(&panel),
+        pointer,
+        pointer_windows.editor_size(),
+    ) {
+        // nothing to dock onto, so it'll float with its tab under the pointer, inside the editor
+        // window
+        let size = FLOATING_PANEL_SIZE.min(editor_size);
+        let position = (pointer - FLOATING_GRAB).clamp(Vec2::ZERO, editor_size - size);
+        hovered = Some((
+            DockTarget::Float { position },
+            Rect::from_corners(position, position + size),
+        ));
+    }
+
+    let Ok((mut style, mut background)) = preview.get_single_mut() else {
+        return;
+    };
+    match hovered {
+        Some((target, rect)) => {
+            // the hierarchy shares the preview and turns it red sometimes
+            background.0 = theme.accent.with_a(0.35);
+            style.display = Display::Flex;
+            style.left = Val::Px(rect.min.x / scale);
+            style.top = Val::Px(rect.min.y / scale);
+            style.width = Val::Px(rect.width() / scale);
+            style.height = Val::Px(rect.height() / scale);
+            tab_drag.target = Some(target);
+        }
+        // still over the tab it started from, or outside the editor window
+        None => {
+            style.display = Display::None;
+            tab_drag.target = None;
+        }
+    }
+}
+
+pub fn end_tab_drag(
+    mut drag_ends: EventReader<Pointer<DragEnd>>,
+    tabs: Query<&DockTab>,
+    mut tab_drag: ResMut<TabDrag>,
+    mut tree: ResMut<DockTree>,
+    mut preview: Query<&mut Style, With<DropPreview>>,
+) {
+    for drag_end in drag_ends.read() {
+        if drag_end.button != PointerButton::Primary || !tabs.contains(drag_end.target) {
+            continue;
+        }
+        let TabDrag { panel, target } = std::mem::take(&mut *tab_drag);
+        if let (Some(panel), Some(target)) = (panel, target) {
+            tree.move_panel(&panel, target);
+        }
+        if let Ok(mut style) = preview.get_single_mut() {
+            style.display = Display::None;
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use bevy::prelude::*;
+
+use crate::EditorSettings;
+
+/// Font and colors shared by everything the editor spawns.
+#[derive(Resource, Clone, Debug)]
+pub struct EditorTheme {
+    pub font: Handle<Font>,
+    pub icon: Handle<Image>,
+    pub window_background: Color,
+    pub panel_background: Color,
+    pub viewport_background: Color,
+    pub input_background: Color,
+    /// hovered splitters, drop previews and other things that need to stand out
+    pub accent: Color,
+    /// drops that aren't allowed and other things that went wrong
+    pub error: Color,
+}
+
+impl FromWorld for EditorTheme {
+    fn from_world(world: &mut World) -> Self {
+        let settings = world.resource::<EditorSettings>().clone();
+        let asset_server = world.resource::<AssetServer>();
+        Self {
+            font: asset_server.load(settings.font),
+            icon: asset_server.load(settings.icon),
+            window_background: Color::hex("39393e").unwrap(),
+            panel_background: Color::hex("232326").unwrap(),
+            viewport_background: Color::hex("2b2c2f").unwrap(),
+            input_background: Color::hex("18181a").unwrap(),
+            accent: Color::hex("4a6fc1").unwrap(),
+            error: Color::hex("c14a4a").unwrap(),
+        }
+    }
+}
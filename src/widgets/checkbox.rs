@@ -0,0 +1,99 @@
+//! a box with a ✓ in it or not, flipped by clicking it.
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use bevy_mod_picking::prelude::*;
+
+use crate::theme::EditorTheme;
+
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Checkbox {
+    pub checked: bool,
+}
+
+/// The ✓ inside a [`Checkbox`].
+#[derive(Component, Debug)]
+pub struct CheckboxMark;
+
+/// Sent when a [`Checkbox`] gets clicked.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CheckboxChanged {
+    pub entity: Entity,
+    pub checked: bool,
+}
+
+fn mark(checked: bool) -> &'static str {
+    if checked {
+        "✓"
+    } else {
+        ""
+    }
+}
+
+pub fn spawn_checkbox<'w, 's, 'a>(
+    builder: &'a mut ChildBuilder<'w, 's, '_>,
+    theme: &EditorTheme,
+    checked: bool,
+) -> EntityCommands<'w, 's, 'a> {
+    let mut commands = builder.spawn((
+        NodeBundle {
+            style: Style {
+                display: Display::Flex,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                width: Val::Px(18.0),
+                height: Val::Px(18.0),
+                ..default()
+            },
+            background_color: BackgroundColor(theme.input_background),
+            ..default()
+        },
+        Checkbox { checked },
+    ));
+    commands.with_children(|builder| {
+        builder.spawn((
+            TextBundle::from_section(
+                mark(checked),
+                TextStyle {
+                    font: theme.font.clone(),
+                    font_size: 14.3,
+                    color: Color::WHITE,
+                },
+            ),
+            CheckboxMark,
+            Pickable::IGNORE,
+        ));
+    });
+    commands
+}
+
+pub fn toggle_checkboxes(
+    mut clicks: EventReader<Pointer<Click>>,
+    mut checkboxes: Query<&mut Checkbox>,
+    mut changes: EventWriter<CheckboxChanged>,
+) {
+    for click in clicks.read() {
+        if click.button != PointerButton::Primary {
+            continue;
+        }
+        if let Ok(mut checkbox) = checkboxes.get_mut(click.target) {
+            checkbox.checked = !checkbox.checked;
+            changes.send(CheckboxChanged {
+                entity: click.target,
+                checked: checkbox.checked,
+            });
+        }
+    }
+}
+
+pub fn update_checkboxes(
+    checkboxes: Query<(&Checkbox, &Children), Changed<Checkbox>>,
+    mut marks: Query<&mut Text, With<CheckboxMark>>,
+) {
+    for (checkbox, children) in &checkboxes {
+        for child in children {
+            if let Ok(mut text) = marks.get_mut(*child) {
+                text.sections[0].value = mark(checkbox.checked).to_string();
+            }
+        }
+    }
+}
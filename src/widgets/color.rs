@@ -0,0 +1,460 @@
+//! a swatch showing a `Color`. clicking it opens a [`Popup`] with an HSV picker: a square picking
+//! saturation (left to right) and value (bottom to top), a strip picking the hue, and sliders for
+//! those and alpha. the color changes as they're dragged, there's nothing to confirm.
+//!
+//! everything here is in sRGB, whatever color space the `Color` was in.
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy_mod_picking::prelude::*;
+
+use super::numeric::{spawn_numeric_field, Number, NumericField, ValueChanged};
+use super::popup::{popup_position, spawn_popup, Popup};
+use crate::floating::PointerWindows;
+use crate::theme::EditorTheme;
+
+/// Pixels per side of the saturation/value square's image, it gets stretched.
+const SQUARE_RESOLUTION: u32 = 64;
+const SQUARE_SIZE: f32 = 144.0;
+
+/// A swatch that opens a color picker.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct ColorField {
+    pub color: Color,
+}
+
+/// Sent when the color of a [`ColorField`] gets changed with its picker.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct ColorChanged {
+    pub entity: Entity,
+    pub color: Color,
+}
+
+/// An open color picker, on its popup.
+#[derive(Component, Clone, Debug)]
+pub struct ColorPicker {
+    /// the [`ColorField`] it's editing
+    pub field: Entity,
+    /// hue in degrees, then saturation, value and alpha from 0 to 1
+    pub hsva: [f32; 4],
+    square: Handle<Image>,
+    /// the hue `square` was last drawn for
+    drawn_hue: f32,
+}
+
+/// The parts of a [`ColorPicker`] that get dragged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorPickerArea {
+    SaturationValue,
+    Hue,
+}
+
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ColorPickerPart {
+    pub picker: Entity,
+    pub area: ColorPickerArea,
+}
+
+/// The marker showing where the picked color is in a [`ColorPickerArea`].
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ColorPickerCursor {
+    pub picker: Entity,
+    pub area: ColorPickerArea,
+}
+
+/// One of a [`ColorPicker`]'s sliders, `channel` indexes into its `hsva`.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ColorPickerSlider {
+    pub picker: Entity,
+    pub channel: usize,
+}
+
+pub fn rgb_to_hsv([r, g, b]: [f32; 3]) -> [f32; 3] {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let hue = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    let saturation = if max > 0.0 { delta / max } else { 0.0 };
+    [hue, saturation, max]
+}
+
+pub fn hsv_to_rgb([hue, saturation, value]: [f32; 3]) -> [f32; 3] {
+    let chroma = value * saturation;
+    let sector = (hue / 60.0).rem_euclid(6.0);
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let [r, g, b] = match sector as u32 {
+        0 => [chroma, x, 0.0],
+        1 => [x, chroma, 0.0],
+        2 => [0.0, chroma, x],
+        3 => [0.0, x, chroma],
+        4 => [x, 0.0, chroma],
+        _ => [chroma, 0.0, x],
+    };
+    let offset = value - chroma;
+    [r + offset, g + offset, b + offset]
+}
+
+fn hsva_color([hue, saturation, value, alpha]: [f32; 4]) -> Color {
+    let [r, g, b] = hsv_to_rgb([hue, saturation, value]);
+    Color::rgba(r, g, b, alpha)
+}
+
+/// Close enough that converting back and forth between rgb and hsv doesn't count as a change.
+fn same_color(a: Color, b: Color) -> bool {
+    let (a, b) = (a.as_rgba_f32(), b.as_rgba_f32());
+    a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-4)
+}
+
+/// The picker's hsva for `color`, keeping `hue` for greys since they don't have one.
+fn color_hsva(color: Color, hue: f32) -> [f32; 4] {
+    let [r, g, b, a] = color.as_rgba_f32();
+    let [h, s, v] = rgb_to_hsv([r, g, b]);
+    let h = if s == 0.0 || v == 0.0 { hue } else { h };
+    [h, s, v, a]
+}
+
+fn image(width: u32, height: u32, pixel: impl Fn(u32, u32) -> [f32; 3]) -> Image {
+    let data = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .flat_map(|(x, y)| {
+            let [r, g, b] = pixel(x, y);
+            [r, g, b, 1.0].map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8)
+        })
+        .collect();
+    Image::new(
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
+
+fn square_image(hue: f32) -> Image {
+    let last = (SQUARE_RESOLUTION - 1) as f32;
+    image(SQUARE_RESOLUTION, SQUARE_RESOLUTION, |x, y| {
+        hsv_to_rgb([hue, x as f32 / last, 1.0 - y as f32 / last])
+    })
+}
+
+fn hue_image() -> Image {
+    let last = (SQUARE_RESOLUTION - 1) as f32;
+    image(1, SQUARE_RESOLUTION, |_, y| {
+        hsv_to_rgb([y as f32 / last * 360.0, 1.0, 1.0])
+    })
+}
+
+pub fn spawn_color_field<'w, 's, 'a>(
+    builder: &'a mut ChildBuilder<'w, 's, '_>,
+    color: Color,
+) -> EntityCommands<'w, 's, 'a> {
+    builder.spawn((
+        NodeBundle {
+            style: Style {
+                width: Val::Px(36.0),
+                height: Val::Px(18.0),
+                ..default()
+            },
+            background_color: BackgroundColor(color),
+            ..default()
+        },
+        ColorField { color },
+    ))
+}
+
+fn spawn_picker(
+    commands: &mut Commands,
+    theme: &EditorTheme,
+    images: &mut Assets<Image>,
+    field: Entity,
+    color: Color,
+    position: Vec2,
+) {
+    let hsva = color_hsva(color, 0.0);
+    let square = images.add(square_image(hsva[0]));
+    let hue = images.add(hue_image());
+    let mut popup = spawn_popup(commands, theme, field, position);
+    let picker = popup.id();
+    popup.insert(ColorPicker {
+        field,
+        hsva,
+        square: square.clone(),
+        drawn_hue: hsva[0],
+    });
+    popup.with_children(|builder| {
+        builder
+            .spawn(NodeBundle {
+                style: Style {
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(4.8),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|builder| {
+                for (area, image, width) in [
+                    (ColorPickerArea::SaturationValue, square, SQUARE_SIZE),
+                    (ColorPickerArea::Hue, hue, 14.0),
+                ] {
+                    builder
+                        .spawn((
+                            ImageBundle {
+                                style: Style {
+                                    width: Val::Px(width),
+                                    height: Val::Px(SQUARE_SIZE),
+                                    ..default()
+                                },
+                                image: UiImage::new(image),
+                                ..default()
+                            },
+                            ColorPickerPart { picker, area },
+                        ))
+                        .with_children(|builder| {
+                            // placed by update_color_pickers
+                            let (width, height) = match area {
+                                ColorPickerArea::SaturationValue => (Val::Px(6.0), Val::Px(6.0)),
+                                ColorPickerArea::Hue => (Val::Percent(100.0), Val::Px(2.0)),
+                            };
+                            builder.spawn((
+                                NodeBundle {
+                                    style: Style {
+                                        position_type: PositionType::Absolute,
+                                        width,
+                                        height,
+                                        ..default()
+                                    },
+                                    background_color: BackgroundColor(Color::WHITE),
+                                    ..default()
+                                },
+                                ColorPickerCursor { picker, area },
+                                Pickable::IGNORE,
+                            ));
+                        });
+                }
+            });
+        for (channel, (label, field)) in [
+            ("H", NumericField::f32(hsva[0]).with_range(0.0, 360.0)),
+            ("S", NumericField::f32(hsva[1]).with_range(0.0, 1.0)),
+            ("V", NumericField::f32(hsva[2]).with_range(0.0, 1.0)),
+            ("A", NumericField::f32(hsva[3]).with_range(0.0, 1.0)),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            builder
+                .spawn(NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Row,
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(4.8),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|builder| {
+                    builder.spawn(TextBundle {
+                        text: Text::from_section(
+                            label,
+                            TextStyle {
+                                font: theme.font.clone(),
+                                font_size: 14.3,
+                                color: Color::WHITE,
+                            },
+                        ),
+                        style: Style {
+                            min_width: Val::Px(14.0),
+                            ..default()
+                        },
+                        ..default()
+                    });
+                    spawn_numeric_field(builder, theme, field)
+                        .insert(ColorPickerSlider { picker, channel })
+                        .insert(Style {
+                            display: Display::Flex,
+                            align_items: AlignItems::Center,
+                            position_type: PositionType::Relative,
+                            flex_grow: 1.0,
+                            height: Val::Px(20.0),
+                            padding: UiRect::horizontal(Val::Px(4.8)),
+                            overflow: Overflow::clip(),
+                            ..default()
+                        });
+                });
+        }
+    });
+}
+
+/// Opens a picker when a color field is clicked, or closes it if it's open.
+pub fn click_color_fields(
+    mut commands: Commands,
+    mut clicks: EventReader<Pointer<Click>>,
+    fields: Query<(&ColorField, &Node, &GlobalTransform)>,
+    popups: Query<(Entity, &Popup)>,
+    mut images: ResMut<Assets<Image>>,
+    theme: Res<EditorTheme>,
+    ui_scale: Res<UiScale>,
+) {
+    for click in clicks.read() {
+        if click.button != PointerButton::Primary {
+            continue;
+        }
+        let Ok((field, node, transform)) = fields.get(click.target) else {
+            continue;
+        };
+        let mut open = false;
+        for (popup, _) in popups.iter().filter(|(_, p)| p.owner == click.target) {
+            commands.entity(popup).despawn_recursive();
+            open = true;
+        }
+        if !open {
+            let position = popup_position(node, transform, &ui_scale);
+            spawn_picker(
+                &mut commands,
+                &theme,
+                &mut images,
+                click.target,
+                field.color,
+                position,
+            );
+        }
+    }
+}
+
+/// Picks from the square and the hue strip where they're pressed and dragged.
+pub fn drag_color_pickers(
+    mut downs: EventReader<Pointer<Down>>,
+    mut drags: EventReader<Pointer<Drag>>,
+    parts: Query<(&ColorPickerPart, &Node, &GlobalTransform)>,
+    mut pickers: Query<&mut ColorPicker>,
+    pointer_windows: PointerWindows,
+) {
+    let presses = downs.read().map(|down| {
+        (
+            down.target,
+            down.button,
+            pointer_windows.position(&down.pointer_location),
+        )
+    });
+    let drags = drags.read().map(|drag| {
+        (
+            drag.target,
+            drag.button,
+            pointer_windows.position(&drag.pointer_location),
+        )
+    });
+    for (target, button, position) in presses.chain(drags) {
+        if button != PointerButton::Primary {
+            continue;
+        }
+        let Ok((part, node, transform)) = parts.get(target) else {
+            continue;
+        };
+        let Ok(mut picker) = pickers.get_mut(part.picker) else {
+            continue;
+        };
+        let rect = node.logical_rect(transform);
+        let relative =
+            ((position - rect.min) / rect.size().max(Vec2::ONE)).clamp(Vec2::ZERO, Vec2::ONE);
+        match part.area {
+            ColorPickerArea::SaturationValue => {
+                picker.hsva[1] = relative.x;
+                picker.hsva[2] = 1.0 - relative.y;
+            }
+            ColorPickerArea::Hue => picker.hsva[0] = relative.y * 360.0,
+        }
+    }
+}
+
+pub fn slide_color_pickers(
+    mut changes: EventReader<ValueChanged>,
+    sliders: Query<&ColorPickerSlider>,
+    mut pickers: Query<&mut ColorPicker>,
+) {
+    for change in changes.read() {
+        let Ok(slider) = sliders.get(change.entity) else {
+            continue;
+        };
+        if let Ok(mut picker) = pickers.get_mut(slider.picker) {
+            picker.hsva[slider.channel] = change.as_f32();
+        }
+    }
+}
+
+/// Redraws pickers that changed, and changes the color of their fields.
+pub fn update_color_pickers(
+    mut pickers: Query<(Entity, &mut ColorPicker), Changed<ColorPicker>>,
+    mut images: ResMut<Assets<Image>>,
+    mut cursors: Query<(&ColorPickerCursor, &mut Style)>,
+    mut sliders: Query<(&ColorPickerSlider, &mut NumericField)>,
+    mut fields: Query<&mut ColorField>,
+    mut changes: EventWriter<ColorChanged>,
+) {
+    for (entity, mut picker) in &mut pickers {
+        let [hue, saturation, value, _] = picker.hsva;
+        if picker.drawn_hue != hue {
+            if let Some(image) = images.get_mut(&picker.square) {
+                *image = square_image(hue);
+            }
+            picker.bypass_change_detection().drawn_hue = hue;
+        }
+        for (cursor, mut style) in &mut cursors {
+            if cursor.picker != entity {
+                continue;
+            }
+            match cursor.area {
+                ColorPickerArea::SaturationValue => {
+                    // centered on the color
+                    style.left = Val::Px(saturation * SQUARE_SIZE - 3.0);
+                    style.top = Val::Px((1.0 - value) * SQUARE_SIZE - 3.0);
+                }
+                ColorPickerArea::Hue => style.top = Val::Px(hue / 360.0 * SQUARE_SIZE - 1.0),
+            }
+        }
+        for (slider, mut field) in &mut sliders {
+            let value = picker.hsva[slider.channel] as f64;
+            if slider.picker == entity && (field.value.as_f64() - value).abs() > 1e-6 {
+                field.value = field.clamp(Number::Float(value));
+            }
+        }
+        // opening it doesn't count as a change, the color comes back the same
+        let color = hsva_color(picker.hsva);
+        if let Ok(mut field) = fields.get_mut(picker.field) {
+            if !same_color(field.color, color) {
+                field.color = color;
+                changes.send(ColorChanged {
+                    entity: picker.field,
+                    color,
+                });
+            }
+        }
+    }
+}
+
+/// Shows the color of fields that changed, and moves their pickers to it if it was changed from
+/// somewhere else.
+pub fn update_color_fields(
+    mut fields: Query<(Entity, &ColorField, &mut BackgroundColor), Changed<ColorField>>,
+    mut pickers: Query<&mut ColorPicker>,
+) {
+    for (entity, field, mut background) in &mut fields {
+        background.0 = field.color;
+        for mut picker in &mut pickers {
+            if picker.field == entity && !same_color(hsva_color(picker.hsva), field.color) {
+                picker.hsva = color_hsva(field.color, picker.hsva[0]);
+            }
+        }
+    }
+}
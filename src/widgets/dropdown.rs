@@ -0,0 +1,176 @@
+//! a button showing one of a list of options. clicking it opens a [`Popup`] with all of them, and
+//! clicking one of those picks it.
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use bevy_mod_picking::prelude::*;
+
+use super::popup::{popup_position, spawn_popup, Popup, PopupEntry};
+use crate::theme::EditorTheme;
+
+#[derive(Component, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Dropdown {
+    pub options: Vec<String>,
+    /// index into `options`
+    pub selected: usize,
+}
+
+impl Dropdown {
+    pub fn selected_option(&self) -> &str {
+        self.options.get(self.selected).map_or("", String::as_str)
+    }
+}
+
+/// The text showing the selected option of a [`Dropdown`].
+#[derive(Component, Debug)]
+pub struct DropdownText;
+
+/// One of the options in an open [`Dropdown`]'s popup.
+#[derive(Component, Debug)]
+pub struct DropdownOption {
+    pub dropdown: Entity,
+    pub index: usize,
+}
+
+/// Sent when an option gets picked from a [`Dropdown`], even the one that was already selected.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct DropdownChanged {
+    pub entity: Entity,
+    pub selected: usize,
+    pub option: String,
+}
+
+fn text_style(theme: &EditorTheme, color: Color) -> TextStyle {
+    TextStyle {
+        font: theme.font.clone(),
+        font_size: 14.3,
+        color,
+    }
+}
+
+pub fn spawn_dropdown<'w, 's, 'a>(
+    builder: &'a mut ChildBuilder<'w, 's, '_>,
+    theme: &EditorTheme,
+    dropdown: Dropdown,
+) -> EntityCommands<'w, 's, 'a> {
+    let selected = dropdown.selected_option().to_string();
+    let mut commands = builder.spawn((
+        NodeBundle {
+            style: Style {
+                display: Display::Flex,
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(4.8),
+                min_width: Val::Px(56.0),
+                height: Val::Px(20.0),
+                padding: UiRect::horizontal(Val::Px(4.8)),
+                ..default()
+            },
+            background_color: BackgroundColor(theme.input_background),
+            ..default()
+        },
+        dropdown,
+    ));
+    commands.with_children(|builder| {
+        builder.spawn((
+            TextBundle::from_section(selected, text_style(theme, Color::WHITE)),
+            DropdownText,
+            Pickable::IGNORE,
+        ));
+        builder.spawn((
+            TextBundle::from_section("▼", text_style(theme, Color::WHITE.with_a(0.5))),
+            Pickable::IGNORE,
+        ));
+    });
+    commands
+}
+
+/// Opens and closes a dropdown's popup when it's clicked, and picks the option clicked in it.
+pub fn click_dropdowns(
+    mut commands: Commands,
+    mut clicks: EventReader<Pointer<Click>>,
+    mut dropdowns: Query<(&mut Dropdown, &Node, &GlobalTransform)>,
+    options: Query<&DropdownOption>,
+    popups: Query<(Entity, &Popup)>,
+    mut changes: EventWriter<DropdownChanged>,
+    theme: Res<EditorTheme>,
+    ui_scale: Res<UiScale>,
+) {
+    let close = |commands: &mut Commands, dropdown: Entity| {
+        for (popup, _) in popups.iter().filter(|(_, popup)| popup.owner == dropdown) {
+            commands.entity(popup).despawn_recursive();
+        }
+    };
+    for click in clicks.read() {
+        if click.button != PointerButton::Primary {
+            continue;
+        }
+        if let Ok(option) = options.get(click.target) {
+            let Ok((mut dropdown, _, _)) = dropdowns.get_mut(option.dropdown) else {
+                continue;
+            };
+            if dropdown.selected != option.index {
+                dropdown.selected = option.index;
+            }
+            changes.send(DropdownChanged {
+                entity: option.dropdown,
+                selected: option.index,
+                option: dropdown.selected_option().to_string(),
+            });
+            close(&mut commands, option.dropdown);
+            continue;
+        }
+        let Ok((dropdown, node, transform)) = dropdowns.get(click.target) else {
+            continue;
+        };
+        if popups.iter().any(|(_, popup)| popup.owner == click.target) {
+            close(&mut commands, click.target);
+            continue;
+        }
+        let position = popup_position(node, transform, &ui_scale);
+        let min_width = node.size().x / ui_scale.0 as f32;
+        spawn_popup(&mut commands, &theme, click.target, position).with_children(|builder| {
+            for (index, option) in dropdown.options.iter().enumerate() {
+                let color = if index == dropdown.selected {
+                    theme.accent
+                } else {
+                    Color::WHITE
+                };
+                builder
+                    .spawn((
+                        NodeBundle {
+                            style: Style {
+                                min_width: Val::Px(min_width),
+                                padding: UiRect::axes(Val::Px(4.8), Val::Px(1.2)),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        DropdownOption {
+                            dropdown: click.target,
+                            index,
+                        },
+                        PopupEntry,
+                    ))
+                    .with_children(|builder| {
+                        builder.spawn((
+                            TextBundle::from_section(option, text_style(&theme, color)),
+                            Pickable::IGNORE,
+                        ));
+                    });
+            }
+        });
+    }
+}
+
+pub fn update_dropdowns(
+    dropdowns: Query<(&Dropdown, &Children), Changed<Dropdown>>,
+    mut texts: Query<&mut Text, With<DropdownText>>,
+) {
+    for (dropdown, children) in &dropdowns {
+        for child in children {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                text.sections[0].value = dropdown.selected_option().to_string();
+            }
+        }
+    }
+}
@@ -0,0 +1,285 @@
+pub mod checkbox;
+pub mod color;
+pub mod dropdown;
+pub mod numeric;
+pub mod popup;
+pub mod text_input;
+pub mod vector;
+
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_mod_picking::prelude::*;
+
+/// The background of the collapsible header that has keyboard focus.
+const FOCUSED_HEADER: Color = Color::rgba(1.0, 1.0, 1.0, 0.06);
+
+/// Which collapsibles are open, keyed by a stable id so it survives the dock getting rebuilt and
+/// can be saved with the workspace.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct CollapsibleStates(pub HashMap<String, bool>);
+
+/// Whether a collapsible is open, on the node holding its header and content. Change `open` to
+/// open or close it from code.
+#[derive(Component, Clone, Debug, PartialEq, Eq)]
+pub struct Collapsible {
+    pub open: bool,
+    /// if set, `open` is kept in [`CollapsibleStates`] under this id, and restored from it when a
+    /// collapsible with the same id gets spawned again
+    pub id: Option<String>,
+}
+
+impl Collapsible {
+    pub fn new(open: bool) -> Self {
+        Self { open, id: None }
+    }
+
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+}
+
+impl Default for Collapsible {
+    fn default() -> Self {
+        Self::new(true)
+    }
+}
+
+/// The clickable row at the top of a [`Collapsible`].
+#[derive(Component, Debug)]
+pub struct CollapsibleHeader;
+
+/// The arrow in a [`CollapsibleHeader`].
+#[derive(Component, Debug)]
+pub struct CollapsibleChevron;
+
+/// The node a [`Collapsible`]'s children are spawned into, hidden while it's closed.
+#[derive(Component, Debug)]
+pub struct CollapsibleContent;
+
+fn chevron(open: bool) -> &'static str {
+    if open {
+        "▼"
+    } else {
+        "▶"
+    }
+}
+
+fn content_display(open: bool) -> Display {
+    if open {
+        Display::Flex
+    } else {
+        Display::None
+    }
+}
+
+pub fn spawn_nested_text_bundle<'w, 's, 'a>(
+    builder: &'a mut ChildBuilder<'w, 's, '_>,
+    font: Handle<Font>,
+    text: &str,
+) -> EntityCommands<'w, 's, 'a> {
+    builder.spawn(TextBundle::from_section(
+        text,
+        TextStyle {
+            font,
+            font_size: 14.3, // web mockup had 12px, for some reason bevy font size doesnt match web, this lines it up
+            color: Color::WHITE,
+        },
+    ))
+}
+
+pub fn spawn_nested_collapsible<'w, 's, 'a>(
+    builder: &'a mut ChildBuilder<'w, 's, '_>,
+    title: &str,
+    font: Handle<Font>,
+    collapsible: Collapsible,
+    spawn_children: impl FnOnce(&mut ChildBuilder),
+) -> EntityCommands<'w, 's, 'a> {
+    let open = collapsible.open;
+    // header on top, content under it
+    let mut commands = builder.spawn((
+        NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                margin: UiRect::bottom(Val::Px(6.0)),
+                ..default()
+            },
+            ..default()
+        },
+        collapsible,
+    ));
+    commands.with_children(|builder| {
+        builder
+            .spawn((
+                ButtonBundle {
+                    style: Style {
+                        width: Val::Percent(100.0),
+                        height: Val::Px(18.0),
+                        display: Display::Flex,
+                        flex_direction: FlexDirection::Row,
+                        justify_content: JustifyContent::SpaceBetween,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    background_color: BackgroundColor(Color::NONE),
+                    ..default()
+                },
+                CollapsibleHeader,
+            ))
+            .with_children(|builder| {
+                // left is just a display:flex row with flexstart justify and center align, it has
+                // the chevron and the name
+                builder
+                    .spawn((
+                        NodeBundle {
+                            style: Style {
+                                display: Display::Flex,
+                                flex_direction: FlexDirection::Row,
+                                justify_content: JustifyContent::FlexStart,
+                                align_items: AlignItems::Center,
+                                column_gap: Val::Px(4.8),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        Pickable::IGNORE,
+                    ))
+                    .with_children(|builder| {
+                        spawn_nested_text_bundle(builder, font.clone(), chevron(open))
+                            .insert((CollapsibleChevron, Pickable::IGNORE));
+                        spawn_nested_text_bundle(builder, font.clone(), title)
+                            .insert(Pickable::IGNORE);
+                    });
+            });
+
+        // content is just margin-left: 0.7rem + 0.4rem + 0.1rem which is 14.4px
+        builder
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        display: content_display(open),
+                        flex_direction: FlexDirection::Column,
+                        justify_content: JustifyContent::FlexStart,
+                        align_items: AlignItems::FlexStart,
+                        row_gap: Val::Px(2.4),
+                        margin: UiRect {
+                            left: Val::Px(14.4),
+                            right: Val::Px(0.0),
+                            top: Val::Px(0.0),
+                            bottom: Val::Px(0.0),
+                        },
+                        ..default()
+                    },
+                    ..default()
+                },
+                CollapsibleContent,
+            ))
+            .with_children(|builder| {
+                // our spawn_children function will be called here
+                spawn_children(builder);
+            });
+    });
+    commands
+}
+
+/// Flips a collapsible when its header is clicked. The last header clicked has keyboard focus:
+/// Space or Enter flips it, Left closes it and Right opens it. Clicking anything else or Escape
+/// drops the focus.
+pub fn toggle_collapsibles(
+    mut clicks: EventReader<Pointer<Click>>,
+    keys: Res<Input<KeyCode>>,
+    mut headers: Query<(&Parent, &mut BackgroundColor), With<CollapsibleHeader>>,
+    mut collapsibles: Query<&mut Collapsible>,
+    mut focused: Local<Option<Entity>>,
+) {
+    let mut focus = *focused;
+    for click in clicks.read() {
+        if click.button != PointerButton::Primary {
+            continue;
+        }
+        let Ok((parent, _)) = headers.get(click.target) else {
+            focus = None;
+            continue;
+        };
+        focus = Some(click.target);
+        if let Ok(mut collapsible) = collapsibles.get_mut(parent.get()) {
+            collapsible.open = !collapsible.open;
+        }
+    }
+    if keys.just_pressed(KeyCode::Escape) {
+        focus = None;
+    }
+    // it might have been despawned
+    focus = focus.filter(|header| headers.contains(*header));
+
+    if let Some((parent, _)) = focus.and_then(|header| headers.get(header).ok()) {
+        if let Ok(mut collapsible) = collapsibles.get_mut(parent.get()) {
+            let open =
+                if keys.any_just_pressed([KeyCode::Space, KeyCode::Return, KeyCode::NumpadEnter]) {
+                    !collapsible.open
+                } else if keys.just_pressed(KeyCode::Left) {
+                    false
+                } else if keys.just_pressed(KeyCode::Right) {
+                    true
+                } else {
+                    collapsible.open
+                };
+            // only touch it when it actually flips, so it doesn't count as changed
+            if collapsible.open != open {
+                collapsible.open = open;
+            }
+        }
+    }
+
+    if focus != *focused {
+        if let Some((_, mut background)) = focused.and_then(|header| headers.get_mut(header).ok()) {
+            background.0 = Color::NONE;
+        }
+        if let Some((_, mut background)) = focus.and_then(|header| headers.get_mut(header).ok()) {
+            background.0 = FOCUSED_HEADER;
+        }
+        *focused = focus;
+    }
+}
+
+/// Shows or hides the content and turns the chevron of collapsibles that were opened or closed,
+/// and keeps [`CollapsibleStates`] up to date. Collapsibles that were just spawned with an id get
+/// their state from it first.
+pub fn update_collapsibles(
+    mut states: ResMut<CollapsibleStates>,
+    mut collapsibles: Query<(&mut Collapsible, &Children), Changed<Collapsible>>,
+    mut contents: Query<&mut Style, With<CollapsibleContent>>,
+    headers: Query<&Children, With<CollapsibleHeader>>,
+    rows: Query<&Children>,
+    mut chevrons: Query<&mut Text, With<CollapsibleChevron>>,
+) {
+    for (mut collapsible, children) in &mut collapsibles {
+        if let Some(id) = collapsible.id.clone() {
+            match states.0.get(&id) {
+                Some(open) if collapsible.is_added() => collapsible.open = *open,
+                Some(open) if *open == collapsible.open => {}
+                // don't save the state they were spawned in, only what they were changed to
+                _ if collapsible.is_added() => {}
+                _ => {
+                    states.0.insert(id, collapsible.open);
+                }
+            }
+        }
+        for child in children {
+            if let Ok(mut style) = contents.get_mut(*child) {
+                style.display = content_display(collapsible.open);
+            }
+            // header > row > chevron
+            for row in headers.get(*child).into_iter().flatten() {
+                for chevron_entity in rows.get(*row).into_iter().flatten() {
+                    if let Ok(mut text) = chevrons.get_mut(*chevron_entity) {
+                        text.sections[0].value = chevron(collapsible.open).to_string();
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,498 @@
+//! a box with a number in it. drag it left and right to scrub the number, holding Shift for smaller
+//! steps and Alt for bigger ones. double-click it to type one in, it's a [`TextInput`] while that's
+//! going on. with a finite `min` and `max` it's a slider, with a bar filling up as the number goes
+//! from one to the other.
+//!
+//! ```no_run
+//! use bevy::prelude::*;
+//! use bevy_editor_real::widgets::numeric::{spawn_numeric_field, NumericField, ValueChanged};
+//! use bevy_editor_real::EditorTheme;
+//!
+//! fn spawn_volume(builder: &mut ChildBuilder, theme: &EditorTheme) {
+//!     spawn_numeric_field(builder, theme, NumericField::f32(0.8).with_range(0.0, 1.0));
+//! }
+//!
+//! fn set_volume(mut changes: EventReader<ValueChanged>) {
+//!     for change in changes.read() {
+//!         info!("volume is {} now", change.as_f32());
+//!     }
+//! }
+//! ```
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use bevy_mod_picking::prelude::*;
+
+use super::text_input::{
+    TextInput, TextInputCancelled, TextInputFocus, TextInputSubmitted, TextInputText,
+};
+use crate::theme::EditorTheme;
+
+/// How close together two clicks have to be to count as a double-click, in seconds.
+const DOUBLE_CLICK_TIME: f64 = 0.4;
+
+/// Which numbers a [`NumericField`] takes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NumericKind {
+    F32,
+    /// whole numbers from `min` to `max`, wide enough for any integer up to 64 bits
+    Integer {
+        min: i128,
+        max: i128,
+    },
+}
+
+/// What a [`NumericField`] holds. Integers don't go through an `f64`, which can't hold every
+/// `i64` or `u64`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Number {
+    Float(f64),
+    Integer(i128),
+}
+
+impl Number {
+    pub fn as_f64(self) -> f64 {
+        match self {
+            Number::Float(value) => value,
+            Number::Integer(value) => value as f64,
+        }
+    }
+
+    /// Rounded to the nearest whole number if it's a float.
+    pub fn as_i128(self) -> i128 {
+        match self {
+            // float to int `as` saturates, and turns NaN into 0
+            Number::Float(value) => value.round() as i128,
+            Number::Integer(value) => value,
+        }
+    }
+}
+
+impl Default for Number {
+    fn default() -> Self {
+        Number::Float(0.0)
+    }
+}
+
+/// A number that can be scrubbed and typed in, see the module docs.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct NumericField {
+    /// always a [`Number::Integer`] for [`NumericKind::Integer`]
+    pub value: Number,
+    pub kind: NumericKind,
+    /// where the slider starts and ends, integers also stop at their own min and max
+    pub min: f64,
+    pub max: f64,
+    /// how much one pixel of dragging changes the value
+    pub step: f64,
+}
+
+impl NumericField {
+    pub fn f32(value: f32) -> Self {
+        Self {
+            value: Number::Float(value as f64),
+            kind: NumericKind::F32,
+            min: f64::NEG_INFINITY,
+            max: f64::INFINITY,
+            step: 0.01,
+        }
+    }
+
+    /// A field for whole numbers from `min` to `max`.
+    pub fn integer(value: i128, min: i128, max: i128) -> Self {
+        Self {
+            value: Number::Integer(value.clamp(min, max)),
+            kind: NumericKind::Integer { min, max },
+            min: f64::NEG_INFINITY,
+            max: f64::INFINITY,
+            step: 0.25,
+        }
+    }
+
+    pub fn i32(value: i32) -> Self {
+        Self::integer(value as i128, i32::MIN as i128, i32::MAX as i128)
+    }
+
+    pub fn u32(value: u32) -> Self {
+        Self::integer(value as i128, 0, u32::MAX as i128)
+    }
+
+    pub fn i64(value: i64) -> Self {
+        Self::integer(value as i128, i64::MIN as i128, i64::MAX as i128)
+    }
+
+    pub fn u64(value: u64) -> Self {
+        Self::integer(value as i128, 0, u64::MAX as i128)
+    }
+
+    /// Keeps the value between `min` and `max`, and shows how far along it is with a bar.
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.min = min;
+        self.max = max;
+        // dragging across about 200px goes from one end to the other
+        self.step = (max - min) / 200.0;
+        self.value = self.clamp(self.value);
+        self
+    }
+
+    /// Sets the value, clamped and rounded to fit.
+    pub fn with_value(mut self, value: Number) -> Self {
+        self.value = self.clamp(value);
+        self
+    }
+
+    pub fn with_step(mut self, step: f64) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Whether it's a slider, with a bar.
+    pub fn has_range(&self) -> bool {
+        self.min.is_finite() && self.max.is_finite() && self.max > self.min
+    }
+
+    /// Rounds and clamps to what `kind` can hold, and clamps to the range.
+    pub fn clamp(&self, value: Number) -> Number {
+        match self.kind {
+            NumericKind::F32 => Number::Float(value.as_f64().clamp(self.min, self.max)),
+            NumericKind::Integer { min, max } => Number::Integer(
+                value
+                    .as_i128()
+                    .clamp(min, max)
+                    // infinite ends saturate to i128::MIN and MAX
+                    .clamp(self.min.ceil() as i128, self.max.floor() as i128),
+            ),
+        }
+    }
+
+    pub fn text(&self) -> String {
+        match self.value {
+            Number::Float(value) => format!("{value:.3}"),
+            Number::Integer(value) => format!("{value}"),
+        }
+    }
+}
+
+/// Sent when a [`NumericField`]'s value gets changed by dragging or typing.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct ValueChanged {
+    pub entity: Entity,
+    pub value: Number,
+}
+
+impl ValueChanged {
+    pub fn as_f32(&self) -> f32 {
+        self.value.as_f64() as f32
+    }
+
+    pub fn as_i32(&self) -> i32 {
+        self.value
+            .as_i128()
+            .clamp(i32::MIN as i128, i32::MAX as i128) as i32
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        self.value.as_i128().clamp(0, u32::MAX as i128) as u32
+    }
+
+    pub fn as_i64(&self) -> i64 {
+        self.value
+            .as_i128()
+            .clamp(i64::MIN as i128, i64::MAX as i128) as i64
+    }
+
+    pub fn as_u64(&self) -> u64 {
+        self.value.as_i128().clamp(0, u64::MAX as i128) as u64
+    }
+}
+
+/// How much of the current drag hasn't been added to a [`NumericField`] yet, so slow drags still
+/// add up for integers.
+#[derive(Component, Debug, Default)]
+pub struct NumericFieldDrag {
+    remainder: f64,
+}
+
+/// The bar inside a slider.
+#[derive(Component, Debug)]
+pub struct NumericFieldFill;
+
+/// The text inside a [`NumericField`].
+#[derive(Component, Debug)]
+pub struct NumericFieldText;
+
+pub fn spawn_numeric_field<'w, 's, 'a>(
+    builder: &'a mut ChildBuilder<'w, 's, '_>,
+    theme: &EditorTheme,
+    field: NumericField,
+) -> EntityCommands<'w, 's, 'a> {
+    let mut commands = builder.spawn((
+        NodeBundle {
+            style: Style {
+                display: Display::Flex,
+                align_items: AlignItems::Center,
+                position_type: PositionType::Relative,
+                min_width: Val::Px(56.0),
+                height: Val::Px(20.0),
+                padding: UiRect::horizontal(Val::Px(4.8)),
+                overflow: Overflow::clip(),
+                ..default()
+            },
+            background_color: BackgroundColor(theme.input_background),
+            ..default()
+        },
+        field,
+        NumericFieldDrag::default(),
+    ));
+    commands.with_children(|builder| {
+        builder.spawn((
+            NodeBundle {
+                style: Style {
+                    display: if field.has_range() {
+                        Display::Flex
+                    } else {
+                        Display::None
+                    },
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(0.0),
+                    top: Val::Px(0.0),
+                    height: Val::Percent(100.0),
+                    width: Val::Percent(0.0),
+                    ..default()
+                },
+                background_color: BackgroundColor(theme.accent.with_a(0.5)),
+                ..default()
+            },
+            NumericFieldFill,
+            Pickable::IGNORE,
+        ));
+        builder.spawn((
+            TextBundle::from_section(
+                field.text(),
+                TextStyle {
+                    font: theme.font.clone(),
+                    font_size: 14.3,
+                    color: Color::WHITE,
+                },
+            ),
+            NumericFieldText,
+            TextInputText,
+            Pickable::IGNORE,
+        ));
+    });
+    commands
+}
+
+/// Scrubs fields as they're dragged.
+pub fn drag_numeric_fields(
+    mut drag_starts: EventReader<Pointer<DragStart>>,
+    mut drags: EventReader<Pointer<Drag>>,
+    mut fields: Query<(&mut NumericField, &mut NumericFieldDrag), Without<TextInput>>,
+    keys: Res<Input<KeyCode>>,
+    mut changes: EventWriter<ValueChanged>,
+) {
+    for drag_start in drag_starts.read() {
+        if let Ok((_, mut dragged)) = fields.get_mut(drag_start.target) {
+            dragged.remainder = 0.0;
+        }
+    }
+    for drag in drags.read() {
+        if drag.button != PointerButton::Primary {
+            continue;
+        }
+        let Ok((mut field, mut dragged)) = fields.get_mut(drag.target) else {
+            continue;
+        };
+        let multiplier = if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+            0.1
+        } else if keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]) {
+            10.0
+        } else {
+            1.0
+        };
+        dragged.remainder += drag.delta.x as f64 * field.step * multiplier;
+        let value = match field.value {
+            Number::Float(value) => Number::Float(value + std::mem::take(&mut dragged.remainder)),
+            Number::Integer(value) => {
+                let whole = dragged.remainder.trunc();
+                dragged.remainder -= whole;
+                Number::Integer(value.saturating_add(whole as i128))
+            }
+        };
+        let value = field.clamp(value);
+        if value != field.value {
+            field.value = value;
+            changes.send(ValueChanged {
+                entity: drag.target,
+                value,
+            });
+        }
+    }
+}
+
+fn numeric_char(c: char) -> bool {
+    c.is_ascii_digit() || ".-+eE".contains(c)
+}
+
+/// Starts typing into a field when it's double-clicked, with the number selected.
+pub fn click_numeric_fields(
+    mut commands: Commands,
+    mut downs: EventReader<Pointer<Down>>,
+    mut drag_starts: EventReader<Pointer<DragStart>>,
+    mut clicks: EventReader<Pointer<Click>>,
+    fields: Query<&NumericField, Without<TextInput>>,
+    time: Res<Time>,
+    mut focus: ResMut<TextInputFocus>,
+    // clicks at the end of a drag don't count
+    mut dragged: Local<bool>,
+    mut last_click: Local<Option<(Entity, f64)>>,
+) {
+    if downs.read().count() > 0 {
+        *dragged = false;
+    }
+    if drag_starts.read().count() > 0 {
+        *dragged = true;
+        *last_click = None;
+    }
+    let now = time.elapsed_seconds_f64();
+    for click in clicks.read() {
+        if click.button != PointerButton::Primary || *dragged {
+            continue;
+        }
+        let Ok(field) = fields.get(click.target) else {
+            *last_click = None;
+            continue;
+        };
+        match *last_click {
+            Some((entity, at)) if entity == click.target && now - at < DOUBLE_CLICK_TIME => {
+                let mut input = TextInput::new(field.text()).with_filter(numeric_char);
+                input.select_all();
+                commands.entity(click.target).insert(input);
+                focus.0 = Some(click.target);
+                *last_click = None;
+            }
+            _ => *last_click = Some((click.target, now)),
+        }
+    }
+}
+
+/// Sets the value to what was typed when it's submitted, if it's a number, and stops editing when
+/// it's submitted or cancelled.
+pub fn edit_numeric_fields(
+    mut commands: Commands,
+    mut submitted: EventReader<TextInputSubmitted>,
+    mut cancelled: EventReader<TextInputCancelled>,
+    mut fields: Query<&mut NumericField, With<TextInput>>,
+    mut changes: EventWriter<ValueChanged>,
+) {
+    for submitted in submitted.read() {
+        let Ok(mut field) = fields.get_mut(submitted.entity) else {
+            continue;
+        };
+        commands.entity(submitted.entity).remove::<TextInput>();
+        let text = submitted.text.trim();
+        // integers are parsed as they are so big ones don't get rounded through an f64
+        let value = match (field.kind, text.parse::<i128>()) {
+            (NumericKind::Integer { .. }, Ok(value)) => Number::Integer(value),
+            _ => match text.parse::<f64>() {
+                Ok(value) if value.is_finite() => Number::Float(value),
+                _ => continue,
+            },
+        };
+        let value = field.clamp(value);
+        if value != field.value {
+            field.value = value;
+            changes.send(ValueChanged {
+                entity: submitted.entity,
+                value,
+            });
+        }
+    }
+    for cancelled in cancelled.read() {
+        if fields.contains(cancelled.entity) {
+            commands.entity(cancelled.entity).remove::<TextInput>();
+        }
+    }
+}
+
+/// Keeps the text and the bar showing the value. The text is left alone while it's being typed into.
+pub fn update_numeric_fields(
+    changed: Query<Entity, Changed<NumericField>>,
+    mut stopped_editing: RemovedComponents<TextInput>,
+    fields: Query<(&NumericField, Option<&TextInput>, &Children)>,
+    mut texts: Query<&mut Text, With<NumericFieldText>>,
+    mut fills: Query<&mut Style, With<NumericFieldFill>>,
+) {
+    for entity in changed.iter().chain(stopped_editing.read()) {
+        let Ok((field, editing, children)) = fields.get(entity) else {
+            continue;
+        };
+        for child in children {
+            if let (Ok(mut text), None) = (texts.get_mut(*child), editing) {
+                // the text input might have split it into sections
+                text.sections.truncate(1);
+                text.sections[0].value = field.text();
+                text.sections[0].style.color = Color::WHITE;
+            }
+            if let Ok(mut style) = fills.get_mut(*child) {
+                if field.has_range() {
+                    let fraction = (field.value.as_f64() - field.min) / (field.max - field.min);
+                    style.display = Display::Flex;
+                    style.width = Val::Percent(fraction as f32 * 100.0);
+                } else {
+                    style.display = Display::None;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_stop_at_their_own_min_and_max() {
+        let byte = NumericField::integer(0, u8::MIN as i128, u8::MAX as i128);
+        assert_eq!(byte.clamp(Number::Integer(300)), Number::Integer(255));
+        assert_eq!(byte.clamp(Number::Float(-4.0)), Number::Integer(0));
+        assert_eq!(byte.clamp(Number::Float(2.6)), Number::Integer(3));
+        let small = NumericField::integer(0, i8::MIN as i128, i8::MAX as i128);
+        assert_eq!(small.clamp(Number::Integer(-1000)), Number::Integer(-128));
+    }
+
+    #[test]
+    fn wide_integers_keep_every_digit() {
+        let big = NumericField::u64(u64::MAX);
+        assert_eq!(big.value, Number::Integer(u64::MAX as i128));
+        assert_eq!(big.text(), "18446744073709551615");
+        // 2^53 + 1 doesn't fit in an f64
+        let odd = NumericField::i64(0).with_value(Number::Integer((1 << 53) + 1));
+        assert_eq!(odd.text(), "9007199254740993");
+        assert_eq!(
+            NumericField::i64(0).clamp(Number::Float(f64::INFINITY)),
+            Number::Integer(i64::MAX as i128)
+        );
+    }
+
+    #[test]
+    fn ranges_clamp_within_the_kind() {
+        let field = NumericField::i32(0).with_range(-10.5, 10.5);
+        assert_eq!(field.clamp(Number::Integer(20)), Number::Integer(10));
+        assert_eq!(field.clamp(Number::Integer(-20)), Number::Integer(-10));
+        let field = NumericField::f32(0.0).with_range(0.0, 1.0);
+        assert_eq!(field.clamp(Number::Float(1.5)), Number::Float(1.0));
+        assert_eq!(field.clamp(Number::Integer(-3)), Number::Float(0.0));
+    }
+
+    #[test]
+    fn value_changed_saturates() {
+        let change = |value| ValueChanged {
+            entity: Entity::PLACEHOLDER,
+            value,
+        };
+        assert_eq!(change(Number::Integer(-5)).as_u32(), 0);
+        assert_eq!(change(Number::Integer(i64::MAX as i128)).as_i32(), i32::MAX);
+        assert_eq!(change(Number::Integer(u64::MAX as i128)).as_u64(), u64::MAX);
+        assert_eq!(change(Number::Float(1.5)).as_f32(), 1.5);
+    }
+}
@@ -0,0 +1,112 @@
+//! nodes floating over the rest of the editor, opened from a widget like a dropdown or a color
+//! field. they're spawned at the root like the menus' dropdowns, so the panel the widget is in
+//! doesn't clip them. clicking outside a popup (other than on the widget that opened it) or
+//! pressing Escape closes it, and so does the widget getting despawned.
+use bevy::ecs::entity::Entities;
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use bevy_mod_picking::prelude::*;
+
+use crate::scene::EditorOnly;
+use crate::theme::EditorTheme;
+
+/// A popup opened from the widget `owner`.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Popup {
+    pub owner: Entity,
+}
+
+/// Something in a popup that can be picked, like a dropdown's options. It gets highlighted while
+/// hovered.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct PopupEntry;
+
+/// Where a popup for a widget goes: right under it.
+pub fn popup_position(node: &Node, transform: &GlobalTransform, ui_scale: &UiScale) -> Vec2 {
+    let rect = node.logical_rect(transform);
+    Vec2::new(rect.min.x, rect.max.y + 2.4) / ui_scale.0 as f32
+}
+
+pub fn spawn_popup<'w, 's, 'a>(
+    commands: &'a mut Commands<'w, 's>,
+    theme: &EditorTheme,
+    owner: Entity,
+    position: Vec2,
+) -> EntityCommands<'w, 's, 'a> {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(position.x),
+                top: Val::Px(position.y),
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(4.8)),
+                row_gap: Val::Px(4.8),
+                ..default()
+            },
+            background_color: BackgroundColor(theme.input_background),
+            // just under the menus
+            z_index: ZIndex::Global(40),
+            ..default()
+        },
+        Popup { owner },
+        EditorOnly,
+    ))
+}
+
+/// The popup `entity` is in, if it's in one.
+pub fn find_popup(
+    mut entity: Entity,
+    popups: &Query<(Entity, &Popup)>,
+    parents: &Query<&Parent>,
+) -> Option<Entity> {
+    loop {
+        if popups.contains(entity) {
+            return Some(entity);
+        }
+        entity = parents.get(entity).ok()?.get();
+    }
+}
+
+pub fn close_popups(
+    mut commands: Commands,
+    mut clicks: EventReader<Pointer<Click>>,
+    keys: Res<Input<KeyCode>>,
+    popups: Query<(Entity, &Popup)>,
+    parents: Query<&Parent>,
+    entities: &Entities,
+) {
+    let clicked: Vec<_> = clicks
+        .read()
+        .map(|click| (click.target, find_popup(click.target, &popups, &parents)))
+        .collect();
+    for (entity, popup) in &popups {
+        // clicks on the widget that opened it are up to the widget
+        let clicked_outside = clicked
+            .iter()
+            .any(|(target, inside)| *inside != Some(entity) && *target != popup.owner);
+        if clicked_outside || keys.just_pressed(KeyCode::Escape) || !entities.contains(popup.owner)
+        {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+pub fn highlight_popup_entries(
+    mut overs: EventReader<Pointer<Over>>,
+    mut outs: EventReader<Pointer<Out>>,
+    mut entries: Query<&mut BackgroundColor, With<PopupEntry>>,
+    theme: Res<EditorTheme>,
+) {
+    for over in overs.read() {
+        if let Ok(mut background) = entries.get_mut(over.target) {
+            background.0 = theme.window_background;
+        }
+    }
+    for out in outs.read() {
+        if let Ok(mut background) = entries.get_mut(out.target) {
+            background.0 = Color::NONE;
+        }
+    }
+}
@@ -0,0 +1,573 @@
+//! a single line text field. click one to focus it and type into it: the arrow keys, Home and End
+//! move the caret (Shift selects), Backspace, Delete and the arrow keys repeat while held, Ctrl+A
+//! selects everything, and Ctrl+C, Ctrl+X and Ctrl+V go through the system clipboard. Enter
+//! submits it and Escape cancels it, both unfocus it, and so does clicking anything else (which
+//! counts as submitting).
+//!
+//! ```no_run
+//! use bevy::prelude::*;
+//! use bevy_editor_real::widgets::text_input::{spawn_text_input, TextInput, TextInputSubmitted};
+//! use bevy_editor_real::EditorTheme;
+//!
+//! fn spawn_name_field(builder: &mut ChildBuilder, theme: &EditorTheme) {
+//!     spawn_text_input(builder, theme, TextInput::default().with_placeholder("Name"));
+//! }
+//!
+//! fn rename(mut submitted: EventReader<TextInputSubmitted>) {
+//!     for submitted in submitted.read() {
+//!         info!("renamed to {}", submitted.text);
+//!     }
+//! }
+//! ```
+use std::ops::Range;
+
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use bevy_mod_picking::prelude::*;
+
+use crate::theme::EditorTheme;
+
+/// How long Backspace, Delete or an arrow key has to be held before it starts repeating, in
+/// seconds.
+const KEY_REPEAT_DELAY: f64 = 0.5;
+
+/// How long it waits between repeats after that, in seconds.
+const KEY_REPEAT_INTERVAL: f64 = 0.035;
+
+/// Text that can be typed into, see the module docs.
+#[derive(Component, Clone, Debug, Default)]
+pub struct TextInput {
+    pub text: String,
+    /// shown greyed out while `text` is empty
+    pub placeholder: String,
+    /// where typing goes, as a byte offset into `text`
+    pub caret: usize,
+    /// the other end of the selection from the caret, if anything is selected
+    pub anchor: Option<usize>,
+    /// characters it returns false for can't be typed or pasted in, everything can if it's `None`
+    pub filter: Option<fn(char) -> bool>,
+}
+
+impl TextInput {
+    /// With the caret at the end.
+    pub fn new(text: impl Into<String>) -> Self {
+        let text = text.into();
+        Self {
+            caret: text.len(),
+            text,
+            ..default()
+        }
+    }
+
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    pub fn with_filter(mut self, filter: fn(char) -> bool) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Replaces the text, putting the caret at the end.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.caret = self.text.len();
+        self.anchor = None;
+    }
+
+    /// The selected byte range, if anything is selected.
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor.filter(|anchor| *anchor != self.caret)?;
+        Some(anchor.min(self.caret)..anchor.max(self.caret))
+    }
+
+    pub fn selected_text(&self) -> &str {
+        self.selection()
+            .map_or("", |selection| &self.text[selection])
+    }
+
+    pub fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.caret = self.text.len();
+    }
+
+    /// Moves the caret, selecting what it moves over if `select` is set.
+    pub fn move_caret(&mut self, to: usize, select: bool) {
+        if !select {
+            self.anchor = None;
+        } else if self.anchor.is_none() {
+            self.anchor = Some(self.caret);
+        }
+        self.caret = to;
+    }
+
+    /// Types `text` in at the caret, over the selection if there is one.
+    pub fn insert(&mut self, text: &str) {
+        let text: String = text
+            .chars()
+            .filter(|c| !c.is_control() && self.filter.is_none_or(|filter| filter(*c)))
+            .collect();
+        self.delete_selection();
+        self.text.insert_str(self.caret, &text);
+        self.caret += text.len();
+    }
+
+    /// Returns false if nothing was selected.
+    pub fn delete_selection(&mut self) -> bool {
+        let selection = self.selection();
+        self.anchor = None;
+        let Some(selection) = selection else {
+            return false;
+        };
+        self.caret = selection.start;
+        self.text.replace_range(selection, "");
+        true
+    }
+
+    /// Deletes the selection, or the character before the caret.
+    pub fn backspace(&mut self) {
+        if !self.delete_selection() {
+            let start = self.previous_char();
+            self.text.replace_range(start..self.caret, "");
+            self.caret = start;
+        }
+    }
+
+    /// Deletes the selection, or the character after the caret.
+    pub fn delete(&mut self) {
+        if !self.delete_selection() {
+            let end = self.next_char();
+            self.text.replace_range(self.caret..end, "");
+        }
+    }
+
+    /// Moves the caret a character left, or to the start of the selection if it isn't selecting.
+    pub fn move_left(&mut self, select: bool) {
+        let to = match self.selection() {
+            Some(selection) if !select => selection.start,
+            _ => self.previous_char(),
+        };
+        self.move_caret(to, select);
+    }
+
+    /// Moves the caret a character right, or to the end of the selection if it isn't selecting.
+    pub fn move_right(&mut self, select: bool) {
+        let to = match self.selection() {
+            Some(selection) if !select => selection.end,
+            _ => self.next_char(),
+        };
+        self.move_caret(to, select);
+    }
+
+    /// Removes the selected text and returns it, if anything is selected.
+    pub fn cut(&mut self) -> Option<String> {
+        let text = self.selection().map(|_| self.selected_text().to_string());
+        self.delete_selection();
+        text
+    }
+
+    fn previous_char(&self) -> usize {
+        self.text[..self.caret]
+            .chars()
+            .next_back()
+            .map_or(self.caret, |c| self.caret - c.len_utf8())
+    }
+
+    fn next_char(&self) -> usize {
+        self.text[self.caret..]
+            .chars()
+            .next()
+            .map_or(self.caret, |c| self.caret + c.len_utf8())
+    }
+}
+
+/// Which of Backspace, Delete and the arrow keys is being held, and when it repeats next.
+#[derive(Debug, Default)]
+pub struct KeyRepeat {
+    key: Option<KeyCode>,
+    next: f64,
+}
+
+impl KeyRepeat {
+    const KEYS: [KeyCode; 4] = [
+        KeyCode::Back,
+        KeyCode::Delete,
+        KeyCode::Left,
+        KeyCode::Right,
+    ];
+
+    /// The key to act on at `now`: one when it's pressed, and again every so often while it's held.
+    pub fn update(&mut self, keys: &Input<KeyCode>, now: f64) -> Option<KeyCode> {
+        if let Some(key) = Self::KEYS.into_iter().find(|key| keys.just_pressed(*key)) {
+            self.key = Some(key);
+            self.next = now + KEY_REPEAT_DELAY;
+            return Some(key);
+        }
+        self.key = self.key.filter(|key| keys.pressed(*key));
+        let key = self.key.filter(|_| now >= self.next)?;
+        self.next = now + KEY_REPEAT_INTERVAL;
+        Some(key)
+    }
+}
+
+/// The text inside a [`TextInput`].
+#[derive(Component, Debug)]
+pub struct TextInputText;
+
+/// The [`TextInput`] that typing goes into.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TextInputFocus(pub Option<Entity>);
+
+/// Sent whenever a [`TextInput`]'s text gets changed by typing, deleting, cutting or pasting.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct TextInputChanged {
+    pub entity: Entity,
+    pub text: String,
+}
+
+/// Sent when Enter is pressed in a [`TextInput`], or it loses focus by something else getting
+/// clicked.
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct TextInputSubmitted {
+    pub entity: Entity,
+    pub text: String,
+}
+
+/// Sent when Escape is pressed in a [`TextInput`].
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextInputCancelled {
+    pub entity: Entity,
+}
+
+/// The system clipboard. Not every platform has one (or lets it be opened, like Linux without a
+/// display), then it's only shared between the editor's text inputs.
+pub struct EditorClipboard {
+    system: Option<arboard::Clipboard>,
+    fallback: String,
+}
+
+impl Default for EditorClipboard {
+    fn default() -> Self {
+        Self {
+            system: arboard::Clipboard::new().ok(),
+            fallback: String::new(),
+        }
+    }
+}
+
+impl EditorClipboard {
+    pub fn get(&mut self) -> String {
+        self.system
+            .as_mut()
+            .and_then(|clipboard| clipboard.get_text().ok())
+            .unwrap_or_else(|| self.fallback.clone())
+    }
+
+    pub fn set(&mut self, text: &str) {
+        self.fallback = text.to_string();
+        if let Some(clipboard) = &mut self.system {
+            let _ = clipboard.set_text(text);
+        }
+    }
+}
+
+pub fn spawn_text_input<'w, 's, 'a>(
+    builder: &'a mut ChildBuilder<'w, 's, '_>,
+    theme: &EditorTheme,
+    input: TextInput,
+) -> EntityCommands<'w, 's, 'a> {
+    let mut commands = builder.spawn((
+        NodeBundle {
+            style: Style {
+                display: Display::Flex,
+                align_items: AlignItems::Center,
+                width: Val::Percent(100.0),
+                min_height: Val::Px(20.0),
+                padding: UiRect::axes(Val::Px(4.8), Val::Px(2.4)),
+                overflow: Overflow::clip(),
+                ..default()
+            },
+            background_color: BackgroundColor(theme.input_background),
+            ..default()
+        },
+        input,
+    ));
+    commands.with_children(|builder| {
+        // filled in by update_text_inputs
+        builder.spawn((TextBundle::default(), TextInputText, Pickable::IGNORE));
+    });
+    commands
+}
+
+/// Focuses a [`TextInput`] when it's clicked, and submits and unfocuses it when anything else is.
+pub fn focus_text_inputs(
+    mut clicks: EventReader<Pointer<Click>>,
+    inputs: Query<&TextInput>,
+    mut focus: ResMut<TextInputFocus>,
+    mut submitted: EventWriter<TextInputSubmitted>,
+) {
+    let mut focused = focus.0.filter(|entity| inputs.contains(*entity));
+    for click in clicks.read() {
+        if click.button != PointerButton::Primary || focused == Some(click.target) {
+            continue;
+        }
+        if let Some(entity) = focused {
+            if let Ok(input) = inputs.get(entity) {
+                submitted.send(TextInputSubmitted {
+                    entity,
+                    text: input.text.clone(),
+                });
+            }
+        }
+        focused = inputs.contains(click.target).then_some(click.target);
+    }
+    if focus.0 != focused {
+        focus.0 = focused;
+    }
+}
+
+/// Types into the focused [`TextInput`].
+pub fn type_into_text_inputs(
+    mut characters: EventReader<ReceivedCharacter>,
+    keys: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    mut focus: ResMut<TextInputFocus>,
+    mut inputs: Query<&mut TextInput>,
+    mut clipboard: NonSendMut<EditorClipboard>,
+    mut changed: EventWriter<TextInputChanged>,
+    mut submitted: EventWriter<TextInputSubmitted>,
+    mut cancelled: EventWriter<TextInputCancelled>,
+    mut repeat: Local<KeyRepeat>,
+) {
+    let Some((entity, mut input)) = focus
+        .0
+        .and_then(|entity| Some((entity, inputs.get_mut(entity).ok()?)))
+    else {
+        characters.clear();
+        *repeat = KeyRepeat::default();
+        return;
+    };
+    let ctrl = keys.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let text_before = input.text.clone();
+
+    // shortcuts come through as characters too
+    let typed: String = characters.read().map(|character| character.char).collect();
+    if !ctrl && !typed.is_empty() {
+        input.insert(&typed);
+    }
+    match repeat.update(&keys, time.elapsed_seconds_f64()) {
+        Some(KeyCode::Back) => input.backspace(),
+        Some(KeyCode::Delete) => input.delete(),
+        Some(KeyCode::Left) => input.move_left(shift),
+        Some(KeyCode::Right) => input.move_right(shift),
+        _ => {}
+    }
+    if keys.just_pressed(KeyCode::Home) {
+        input.move_caret(0, shift);
+    }
+    if keys.just_pressed(KeyCode::End) {
+        let end = input.text.len();
+        input.move_caret(end, shift);
+    }
+    if ctrl {
+        if keys.just_pressed(KeyCode::A) {
+            input.select_all();
+        }
+        if keys.just_pressed(KeyCode::C) && input.selection().is_some() {
+            clipboard.set(input.selected_text());
+        }
+        if keys.just_pressed(KeyCode::X) {
+            if let Some(cut) = input.cut() {
+                clipboard.set(&cut);
+            }
+        }
+        if keys.just_pressed(KeyCode::V) {
+            // it's a single line
+            let pasted = clipboard.get().replace(['\r', '\n'], " ");
+            input.insert(&pasted);
+        }
+    }
+
+    if input.text != text_before {
+        changed.send(TextInputChanged {
+            entity,
+            text: input.text.clone(),
+        });
+    }
+    if keys.any_just_pressed([KeyCode::Return, KeyCode::NumpadEnter]) {
+        submitted.send(TextInputSubmitted {
+            entity,
+            text: input.text.clone(),
+        });
+        focus.0 = None;
+    } else if keys.just_pressed(KeyCode::Escape) {
+        cancelled.send(TextInputCancelled { entity });
+        focus.0 = None;
+    }
+}
+
+/// Shows the text of inputs that changed or gained or lost focus, with the selection in the accent
+/// color and a caret while focused.
+pub fn update_text_inputs(
+    focus: Res<TextInputFocus>,
+    inputs: Query<(Entity, Ref<TextInput>, &Children)>,
+    mut texts: Query<&mut Text, With<TextInputText>>,
+    theme: Res<EditorTheme>,
+    mut last_focus: Local<Option<Entity>>,
+) {
+    let style = TextStyle {
+        font: theme.font.clone(),
+        font_size: 14.3,
+        color: Color::WHITE,
+    };
+    let section = |text: &str, color: Color| TextSection {
+        value: text.to_string(),
+        style: TextStyle {
+            color,
+            ..style.clone()
+        },
+    };
+    for (entity, input, children) in &inputs {
+        let focused = focus.0 == Some(entity);
+        if !input.is_changed() && !(focus.is_changed() && (focused || *last_focus == Some(entity)))
+        {
+            continue;
+        }
+        let caret = section("|", Color::WHITE);
+        let sections = if input.text.is_empty() {
+            let placeholder = section(&input.placeholder, Color::WHITE.with_a(0.5));
+            if focused {
+                vec![caret, placeholder]
+            } else {
+                vec![placeholder]
+            }
+        } else {
+            let selection = input.selection().unwrap_or(input.caret..input.caret);
+            let mut sections = vec![
+                section(&input.text[..selection.start], Color::WHITE),
+                section(&input.text[selection.clone()], theme.accent),
+                section(&input.text[selection.end..], Color::WHITE),
+            ];
+            if focused {
+                let at = if input.caret == selection.start { 1 } else { 2 };
+                sections.insert(at, caret);
+            }
+            sections
+        };
+        for child in children {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                text.sections.clone_from(&sections);
+            }
+        }
+    }
+    *last_focus = focus.0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn editing_steps_over_whole_characters() {
+        let mut input = TextInput::new("añ🦀");
+        input.backspace();
+        assert_eq!((input.text.as_str(), input.caret), ("añ", 3));
+        input.move_left(false);
+        assert_eq!(input.caret, 1);
+        input.insert("é");
+        assert_eq!((input.text.as_str(), input.caret), ("aéñ", 3));
+        input.delete();
+        assert_eq!((input.text.as_str(), input.caret), ("aé", 3));
+        input.move_caret(0, false);
+        input.delete();
+        input.backspace();
+        assert_eq!((input.text.as_str(), input.caret), ("é", 0));
+    }
+
+    #[test]
+    fn shift_selects() {
+        let mut input = TextInput::new("héllo");
+        input.move_left(true);
+        input.move_left(true);
+        assert_eq!(input.selection(), Some(4..6));
+        assert_eq!(input.selected_text(), "lo");
+        // the anchor stays put
+        input.move_caret(1, true);
+        assert_eq!(input.selected_text(), "éllo");
+        // without Shift the caret jumps to the end of the selection
+        input.move_right(false);
+        assert_eq!((input.caret, input.selection()), (6, None));
+        // going back over where it started flips the selection
+        input.move_caret(3, false);
+        input.move_right(true);
+        input.move_left(true);
+        input.move_left(true);
+        assert_eq!(input.selection(), Some(1..3));
+        input.move_left(false);
+        assert_eq!((input.caret, input.selection()), (1, None));
+    }
+
+    #[test]
+    fn typing_backspace_and_delete_replace_the_selection() {
+        let mut input = TextInput::new("one two");
+        input.move_caret(4, true);
+        input.insert("1 ");
+        assert_eq!((input.text.as_str(), input.caret), ("one 1 ", 6));
+        input.move_caret(0, true);
+        input.backspace();
+        assert_eq!(input.text, "");
+        let mut input = TextInput::new("abc");
+        input.move_caret(1, true);
+        input.delete();
+        assert_eq!((input.text.as_str(), input.caret), ("a", 1));
+    }
+
+    #[test]
+    fn cut_and_paste_replace_the_selection() {
+        let mut input = TextInput::new("copy ünd paste");
+        assert_eq!(input.cut(), None);
+        input.move_caret(5, false);
+        input.move_caret(10, true);
+        assert_eq!(input.cut().as_deref(), Some("ünd "));
+        assert_eq!((input.text.as_str(), input.caret), ("copy paste", 5));
+        input.select_all();
+        input.insert("ünd ");
+        assert_eq!((input.text.as_str(), input.caret), ("ünd ", 5));
+        // the filter applies to pasted text too
+        let mut input = TextInput::new("12").with_filter(|c| c.is_ascii_digit());
+        input.select_all();
+        input.insert("3a4");
+        assert_eq!(input.text, "34");
+    }
+
+    #[test]
+    fn held_keys_repeat() {
+        let mut keys = Input::<KeyCode>::default();
+        let mut repeat = KeyRepeat::default();
+        keys.press(KeyCode::Back);
+        assert_eq!(repeat.update(&keys, 0.0), Some(KeyCode::Back));
+        keys.clear();
+        assert_eq!(repeat.update(&keys, 0.1), None);
+        assert_eq!(repeat.update(&keys, KEY_REPEAT_DELAY), Some(KeyCode::Back));
+        assert_eq!(repeat.update(&keys, KEY_REPEAT_DELAY + 0.01), None);
+        assert_eq!(
+            repeat.update(&keys, KEY_REPEAT_DELAY + KEY_REPEAT_INTERVAL),
+            Some(KeyCode::Back)
+        );
+        // pressing another key takes over
+        keys.press(KeyCode::Left);
+        assert_eq!(repeat.update(&keys, 1.0), Some(KeyCode::Left));
+        keys.clear();
+        keys.release(KeyCode::Left);
+        assert_eq!(repeat.update(&keys, 2.0), None);
+        keys.release(KeyCode::Back);
+        assert_eq!(repeat.update(&keys, 3.0), None);
+    }
+}
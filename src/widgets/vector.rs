@@ -0,0 +1,58 @@
+//! a [`NumericField`] per axis of a vector, next to each other, each with its letter in the axis'
+//! color. each field sends its own [`ValueChanged`](super::numeric::ValueChanged), with a
+//! [`VectorAxis`] on it to tell which one it is.
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+
+use super::numeric::{spawn_numeric_field, NumericField};
+use crate::theme::EditorTheme;
+
+/// The colors of X, Y, Z and W.
+pub const AXIS_COLORS: [Color; 4] = [
+    Color::rgb(0.86, 0.33, 0.33),
+    Color::rgb(0.45, 0.76, 0.29),
+    Color::rgb(0.31, 0.52, 0.9),
+    Color::rgb(0.6, 0.6, 0.6),
+];
+
+const AXIS_NAMES: [&str; 4] = ["X", "Y", "Z", "W"];
+
+/// Which axis of a vector field a [`NumericField`] is, 0 for X.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VectorAxis(pub usize);
+
+/// Spawns a field for each of up to 4 axes. `each` gets called with the index and the commands of
+/// every field, to insert whatever's needed to know what it edits.
+pub fn spawn_vector_field(
+    builder: &mut ChildBuilder,
+    theme: &EditorTheme,
+    axes: &[NumericField],
+    mut each: impl FnMut(usize, &mut EntityCommands),
+) {
+    builder
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(4.8),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|builder| {
+            for (index, field) in axes.iter().take(4).enumerate() {
+                builder.spawn(TextBundle::from_section(
+                    AXIS_NAMES[index],
+                    TextStyle {
+                        font: theme.font.clone(),
+                        font_size: 14.3,
+                        color: AXIS_COLORS[index],
+                    },
+                ));
+                let mut commands = spawn_numeric_field(builder, theme, *field);
+                commands.insert(VectorAxis(index));
+                each(index, &mut commands);
+            }
+        });
+}
@@ -0,0 +1,301 @@
+//! saving the workspace (panel layout, saved presets, open collapsibles, where the viewport camera
+//! is and how it's navigated) to a RON file on exit, and putting it all back on startup.
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::camera::{CameraPivot, FlyCamera, NavigationScheme, ViewportCamera};
+use crate::dock::DockTree;
+use crate::menu::MenuAction;
+use crate::panel::PanelRegistry;
+use crate::presets::{WorkspacePreset, WorkspacePresets};
+use crate::widgets::CollapsibleStates;
+use crate::EditorSettings;
+
+/// Everything that gets saved between sessions.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Workspace {
+    pub dock: DockTree,
+    /// the presets saved from the Window menu, built in ones aren't included
+    #[serde(default)]
+    pub presets: Vec<WorkspacePreset>,
+    /// which preset the layout came from
+    #[serde(default)]
+    pub preset: Option<String>,
+    #[serde(default)]
+    pub collapsibles: HashMap<String, bool>,
+    #[serde(default)]
+    pub camera: Option<Transform>,
+    #[serde(default)]
+    pub navigation: Option<NavigationScheme>,
+    /// the fly cam's speed, see [`FlyCamera::speed`]
+    #[serde(default)]
+    pub camera_speed: Option<f32>,
+}
+
+#[derive(Debug)]
+pub enum WorkspaceError {
+    Io(io::Error),
+    Parse(ron::error::SpannedError),
+    Serialize(ron::Error),
+}
+
+impl fmt::Display for WorkspaceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "couldn't read or write the workspace file: {err}"),
+            Self::Parse(err) => write!(f, "the workspace file is invalid: {err}"),
+            Self::Serialize(err) => write!(f, "couldn't serialize the workspace: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for WorkspaceError {}
+
+impl From<io::Error> for WorkspaceError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for WorkspaceError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+impl From<ron::Error> for WorkspaceError {
+    fn from(err: ron::Error) -> Self {
+        Self::Serialize(err)
+    }
+}
+
+impl Workspace {
+    pub fn load(path: &Path) -> Result<Self, WorkspaceError> {
+        Ok(ron::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), WorkspaceError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let ron = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        fs::write(path, ron)?;
+        Ok(())
+    }
+}
+
+pub fn load_workspace(
+    settings: Res<EditorSettings>,
+    panels: Res<PanelRegistry>,
+    mut tree: ResMut<DockTree>,
+    mut presets: ResMut<WorkspacePresets>,
+    mut collapsibles: ResMut<CollapsibleStates>,
+    mut navigation: ResMut<NavigationScheme>,
+    mut cameras: Query<(
+        &ViewportCamera,
+        &mut Transform,
+        &mut CameraPivot,
+        &mut FlyCamera,
+    )>,
+) {
+    let Some(path) = settings.workspace_path() else {
+        return;
+    };
+    let workspace = match Workspace::load(&path) {
+        Ok(workspace) => workspace,
+        // first launch, nothing saved yet
+        Err(WorkspaceError::Io(err)) if err.kind() == io::ErrorKind::NotFound => return,
+        Err(err) => {
+            warn!("{err}, using the default layout ({})", path.display());
+            return;
+        }
+    };
+
+    let mut dock = workspace.dock;
+    // panels from plugins that aren't around anymore
+    dock.retain_panels(|panel| panels.contains(panel));
+    if dock.is_empty() {
+        warn!(
+            "the saved layout has no panels in it, using the default layout ({})",
+            path.display()
+        );
+    } else {
+        *tree = dock;
+        presets.current = workspace.preset;
+    }
+    for preset in workspace.presets {
+        presets.insert(preset);
+    }
+    collapsibles.0 = workspace.collapsibles;
+    if let Some(scheme) = workspace.navigation {
+        *navigation = scheme;
+    }
+    // only the main viewport's camera is saved, the quad view's others start looking down an axis
+    let Some((_, mut transform, mut pivot, mut fly)) =
+        cameras.iter_mut().find(|(camera, ..)| camera.index == 0)
+    else {
+        return;
+    };
+    if let Some(speed) = workspace.camera_speed {
+        fly.speed = speed.clamp(FlyCamera::MIN_SPEED, FlyCamera::MAX_SPEED);
+    }
+    if let Some(camera) = workspace.camera {
+        // the pivot isn't saved, put it as far in front of the camera as it was from the old spot
+        let distance = transform.translation.distance(pivot.0);
+        *transform = camera;
+        pivot.0 = camera.translation + camera.forward() * distance;
+    }
+}
+
+/// Saves the workspace when the app exits, or when File > Save Workspace is picked.
+pub fn save_workspace(
+    mut exits: EventReader<AppExit>,
+    mut actions: EventReader<MenuAction>,
+    settings: Res<EditorSettings>,
+    tree: Res<DockTree>,
+    presets: Res<WorkspacePresets>,
+    collapsibles: Res<CollapsibleStates>,
+    navigation: Res<NavigationScheme>,
+    cameras: Query<(&ViewportCamera, &Transform, &FlyCamera)>,
+) {
+    let save_requested = actions
+        .read()
+        .any(|action| *action == MenuAction::SaveWorkspace);
+    if exits.read().count() == 0 && !save_requested {
+        return;
+    }
+    let Some(path) = settings.workspace_path() else {
+        return;
+    };
+    let main_camera = cameras.iter().find(|(camera, ..)| camera.index == 0);
+    let workspace = Workspace {
+        dock: tree.clone(),
+        presets: presets.user_presets().cloned().collect(),
+        preset: presets.current.clone(),
+        collapsibles: collapsibles.0.clone(),
+        camera: main_camera.map(|(_, transform, _)| *transform),
+        navigation: Some(*navigation),
+        camera_speed: main_camera.map(|(_, _, fly)| fly.speed),
+    };
+    match workspace.save(&path) {
+        Ok(()) => info!("saved the workspace to {}", path.display()),
+        Err(err) => error!("{err}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::dock::{DockNode, PanelId};
+    use crate::panel::PanelDescriptor;
+
+    /// A path in the temp dir no other test uses, with nothing at it.
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir()
+            .join(format!("bevy_editor_workspace_{}", std::process::id()))
+            .join(name);
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn workspace() -> Workspace {
+        let mut collapsibles = HashMap::default();
+        collapsibles.insert("Transform".to_string(), false);
+        Workspace {
+            dock: DockTree::new(DockNode::tabs(vec![PanelId::INSPECTOR, PanelId::HIERARCHY])),
+            presets: vec![WorkspacePreset::new(
+                "Mine",
+                DockTree::new(DockNode::panel(PanelId::VIEWPORT)),
+            )],
+            preset: Some("Mine".to_string()),
+            collapsibles,
+            camera: Some(Transform::from_xyz(1.0, 2.0, 3.0)),
+            navigation: Some(NavigationScheme::Blender),
+            camera_speed: Some(12.5),
+        }
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let path = temp_path("nested/dir/workspace.ron");
+        let saved = workspace();
+        saved.save(&path).unwrap();
+        let loaded = Workspace::load(&path).unwrap();
+        assert_eq!(loaded.dock, saved.dock);
+        assert_eq!(loaded.presets, saved.presets);
+        assert_eq!(loaded.preset, saved.preset);
+        assert_eq!(loaded.collapsibles, saved.collapsibles);
+        assert_eq!(loaded.camera, saved.camera);
+        assert_eq!(loaded.navigation, saved.navigation);
+        assert_eq!(loaded.camera_speed, saved.camera_speed);
+    }
+
+    #[test]
+    fn load_errors() {
+        let missing = temp_path("missing.ron");
+        assert!(matches!(
+            Workspace::load(&missing),
+            Err(WorkspaceError::Io(err)) if err.kind() == io::ErrorKind::NotFound
+        ));
+        let invalid = temp_path("invalid.ron");
+        fs::create_dir_all(invalid.parent().unwrap()).unwrap();
+        fs::write(&invalid, "(dock: oops").unwrap();
+        assert!(matches!(
+            Workspace::load(&invalid),
+            Err(WorkspaceError::Parse(_))
+        ));
+    }
+
+    /// Runs [`load_workspace`] on the file at `path`, returning the dock it ended up with.
+    fn load_into_app(path: PathBuf, panels: &[PanelId]) -> DockTree {
+        let mut app = App::new();
+        app.insert_resource(EditorSettings::default().with_workspace_file(path))
+            .init_resource::<DockTree>()
+            .init_resource::<WorkspacePresets>()
+            .init_resource::<CollapsibleStates>()
+            .init_resource::<NavigationScheme>()
+            .init_resource::<PanelRegistry>()
+            .add_systems(Update, load_workspace);
+        for panel in panels {
+            app.world
+                .resource_mut::<PanelRegistry>()
+                .register(PanelDescriptor::new(panel.clone(), "", |_, _| {}));
+        }
+        app.update();
+        app.world.resource::<DockTree>().clone()
+    }
+
+    #[test]
+    fn missing_or_invalid_file_keeps_default_layout() {
+        assert_eq!(
+            load_into_app(temp_path("missing.ron"), &[]),
+            DockTree::default()
+        );
+        let invalid = temp_path("broken.ron");
+        fs::create_dir_all(invalid.parent().unwrap()).unwrap();
+        fs::write(&invalid, "not a workspace").unwrap();
+        assert_eq!(load_into_app(invalid, &[]), DockTree::default());
+    }
+
+    #[test]
+    fn loads_saved_layout() {
+        let path = temp_path("saved.ron");
+        let saved = workspace();
+        saved.save(&path).unwrap();
+        assert_eq!(
+            load_into_app(path.clone(), &[PanelId::INSPECTOR, PanelId::HIERARCHY]),
+            saved.dock
+        );
+        // none of its panels are registered anymore
+        assert_eq!(load_into_app(path, &[]), DockTree::default());
+    }
+}